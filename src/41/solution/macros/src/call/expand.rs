@@ -1,13 +1,44 @@
 use super::parse::CallDef;
-use quote::quote;
+use proc_macro2::TokenTree;
+use quote::{quote, quote_spanned, ToTokens};
+use syn::spanned::Spanned;
 
 /// See the `fn call` docs at the `lib.rs` of this crate for a high level definition.
 pub fn expand_call(def: CallDef) -> proc_macro2::TokenStream {
-	let CallDef { pallet_struct, methods } = def;
+	let CallDef { item, pallet_struct, instance, methods } = def;
+
+	// The generics of the `Call` enum, of its `impl` blocks, and of the types we implement for. An
+	// instanced pallet is generic over both `T` and its `'static` instance `I`, and its `Call` enum
+	// defaults the instance to `()`.
+	let (call_generics, impl_generics, type_generics, phantom) = match &instance {
+		Some(i) => (
+			quote!(<T: Config<#i>, #i: 'static = ()>),
+			quote!(<T: Config<#i>, #i: 'static>),
+			quote!(<T, #i>),
+			quote!((T, #i)),
+		),
+		None => (quote!(<T: Config>), quote!(<T: Config>), quote!(<T>), quote!(T)),
+	};
+	// The generics may not be used by any of the calls, for example when every argument is a
+	// `String`, or for the instance. So we always add a hidden variant which uses them. This
+	// variant can never be constructed since it contains `Infallible`.
+	let ignore_variant = quote! {
+		#[doc(hidden)]
+		__Ignore(core::marker::PhantomData<#phantom>, core::convert::Infallible),
+	};
+	// The match arms for the hidden variant, when matching on a `Call` by reference or by value.
+	let ignore_arm = quote!(Call::__Ignore(_, never) => match *never {},);
+	let ignore_owned_arm = quote!(Call::__Ignore(_, never) => match never {},);
 
 	// This is a vector of all the callable function names.
 	let fn_name = methods.iter().map(|method| &method.name).collect::<Vec<_>>();
 
+	// This is a vector of the index of each call, used when encoding the call.
+	let call_index = methods.iter().map(|method| method.index).collect::<Vec<_>>();
+
+	// This is a vector of the doc comments of each function, which we place on each `Call` variant.
+	let fn_docs = methods.iter().map(|method| &method.docs).collect::<Vec<_>>();
+
 	// This is a nested vector of all the arguments for each of the functions in `fn_name`. It does
 	// not include the `self` or `caller: T::AccountId` parameter, which we always assume are the
 	// first two parameters to these calls.
@@ -23,6 +54,61 @@ pub fn expand_call(def: CallDef) -> proc_macro2::TokenStream {
 		.map(|method| method.args.iter().map(|(_, type_)| type_.clone()).collect::<Vec<_>>())
 		.collect::<Vec<_>>();
 
+	// This is a vector of how each function is called when dispatching with the runtime. A function
+	// which takes `runtime: &mut T` is given the runtime, and a function which takes `&mut self` is
+	// called on the pallet found in the runtime. Note that we assume the first argument after these
+	// is always the `caller`.
+	let runtime_fn_call = methods
+		.iter()
+		.map(|method| {
+			let name = &method.name;
+			let args = method.args.iter().map(|(name, _)| name);
+			if method.takes_runtime {
+				quote!(Self::#name(runtime, caller, #( #args ),*))
+			} else {
+				quote! {
+					crate::support::GetPallet::<Self>::pallet_mut(runtime).#name(caller, #( #args ),*)
+				}
+			}
+		})
+		.collect::<Vec<_>>();
+
+	// These are the `where` clauses of all the functions, which we need to dispatch the calls.
+	let where_predicates =
+		methods.iter().flat_map(|method| &method.where_predicates).collect::<Vec<_>>();
+
+	// A pallet where every function takes `&mut self` can also dispatch calls on its own, without
+	// the rest of the runtime. This is useful for testing a pallet by itself.
+	let pallet_dispatch_impl = methods.iter().all(|method| !method.takes_runtime).then(|| {
+		quote! {
+			// Dispatch logic at the pallet level, mapping each of the items in the `Call` enum to the
+			// appropriate function call with all arguments, including the `caller`.
+			impl #impl_generics crate::support::Dispatch for #pallet_struct #type_generics
+			where
+				#( #where_predicates, )*
+			{
+				type Caller = T::AccountId;
+				type Call = Call #type_generics;
+
+				fn dispatch(&mut self, caller: Self::Caller, call: Self::Call) -> crate::support::DispatchResult {
+					match call {
+						#(
+							Call::#fn_name { #( #args_name ),* } => {
+								self.#fn_name(
+									// Note that we assume the first argument of every call is the `caller`.
+									caller,
+									#( #args_name ),*
+								)?;
+							},
+						)*
+						#ignore_owned_arm
+					}
+					Ok(())
+				}
+			}
+		}
+	});
+
 	// This quote block creates an `enum Call` which contains all the calls exposed by our pallet,
 	// and the `Dispatch` trait logic to route a `caller` to access those functions.
 	let dispatch_impl = quote! {
@@ -30,35 +116,300 @@ pub fn expand_call(def: CallDef) -> proc_macro2::TokenStream {
 		//
 		// The parsed function names will be `snake_case`, and that will show up in the enum.
 		#[allow(non_camel_case_types)]
-		pub enum Call<T: Config> {
+		pub enum Call #call_generics {
 			#(
+				#( #fn_docs )*
 				#fn_name { #( #args_name: #args_type),* },
 			)*
+			#ignore_variant
 		}
 
-		// Dispatch logic at the pallet level, mapping each of the items in the `Call` enum to the
-		// appropriate function call with all arguments, including the `caller`.
-		impl<T: Config> crate::support::Dispatch for #pallet_struct<T> {
+		// Dispatch logic at the runtime level, which is used by the runtime to route calls to this
+		// pallet. The runtime must give access to this pallet through `GetPallet`.
+		impl #impl_generics crate::support::DispatchWithRuntime<T> for #pallet_struct #type_generics
+		where
+			T: crate::support::GetPallet<Self>,
+			#( #where_predicates, )*
+		{
 			type Caller = T::AccountId;
-			type Call = Call<T>;
+			type Call = Call #type_generics;
 
-			fn dispatch(&mut self, caller: Self::Caller, call: Self::Call) -> crate::support::DispatchResult {
+			fn dispatch_with_runtime(
+				runtime: &mut T,
+				caller: Self::Caller,
+				call: Self::Call,
+			) -> crate::support::DispatchResult {
 				match call {
 					#(
 						Call::#fn_name { #( #args_name ),* } => {
-							self.#fn_name(
-								// Note that we assume the first argument of every call is the `caller`.
-								caller,
-								#( #args_name ),*
-							)?;
+							#runtime_fn_call?;
 						},
 					)*
+					#ignore_owned_arm
 				}
 				Ok(())
 			}
 		}
+
+		#pallet_dispatch_impl
+	};
+
+	// This is a vector of the argument types which mention the generics of the pallet, like
+	// `T::AccountId`. These must implement `Encode` and `Decode` for us to be able to encode and
+	// decode the `Call` enum, so we add them as bounds on the `impl` blocks. The same goes for the
+	// traits we implement below, like `Debug` and `Clone`.
+	//
+	// Any other argument type, like `Vec<u8>`, is not generic, so a bound on it would always be
+	// false if the type does not implement the trait. Instead, the code which uses each argument is
+	// spanned to its type, so the error points at the argument which lacks the trait.
+	let mut generics = vec![quote::format_ident!("T")];
+	generics.extend(instance.clone());
+	let all_args_type = args_type
+		.iter()
+		.flatten()
+		.filter(|type_| mentions_generics(type_, &generics))
+		.collect::<Vec<_>>();
+
+	// These are nested vectors of the code which uses each argument, for each of the traits below.
+	let debug_field = spanned_args(
+		&methods,
+		|name, type_| quote_spanned!(type_.span()=> .field(stringify!(#name), #name as &dyn core::fmt::Debug)),
+	);
+	let clone_arg = spanned_args(
+		&methods,
+		|name, type_| quote_spanned!(type_.span()=> #name: <#type_ as Clone>::clone(#name)),
+	);
+	let eq_arg = spanned_args(&methods, |name, type_| {
+		let other = other_name(name);
+		quote_spanned!(type_.span()=> && <#type_ as PartialEq>::eq(#name, #other))
+	});
+	let eq_assert = args_type
+		.iter()
+		.flatten()
+		.filter(|type_| !mentions_generics(type_, &generics))
+		.map(|type_| {
+			quote_spanned! {type_.span()=>
+				const _: () = {
+					fn assert_eq<A: Eq + ?Sized>() {}
+					let _ = assert_eq::<#type_>;
+				};
+			}
+		})
+		.collect::<Vec<_>>();
+	let encode_arg = spanned_args(
+		&methods,
+		|name, type_| quote_spanned!(type_.span()=> <#type_ as crate::support::Encode>::encode_to(#name, dest);),
+	);
+	let decode_arg = spanned_args(
+		&methods,
+		|name, type_| quote_spanned!(type_.span()=> #name: <#type_ as crate::support::Decode>::decode(input)?),
+	);
+	let parse_arg = spanned_args(
+		&methods,
+		|name, type_| quote_spanned!(type_.span()=> #name: args.take::<#type_>(stringify!(#name))?),
+	);
+
+	// This is a nested vector of the names we use for the arguments of the `other` call when
+	// comparing two calls.
+	let other_args_name = args_name
+		.iter()
+		.map(|names| names.iter().map(other_name).collect::<Vec<_>>())
+		.collect::<Vec<_>>();
+
+	// This quote block implements `Debug`, `Clone`, `PartialEq` and `Eq` for the `Call` enum.
+	//
+	// We cannot simply use `#[derive]`, since that would require `T` itself to implement these
+	// traits. Instead we only require that the types of the arguments implement them, for example
+	// `T::AccountId` and `T::Balance`.
+	let traits_impl = quote! {
+		impl #impl_generics core::fmt::Debug for Call #type_generics
+		where
+			#( #all_args_type: core::fmt::Debug, )*
+		{
+			fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+				match self {
+					#(
+						Call::#fn_name { #( #args_name ),* } => formatter
+							.debug_struct(stringify!(#fn_name))
+							#( #debug_field )*
+							.finish(),
+					)*
+					#ignore_arm
+				}
+			}
+		}
+
+		impl #impl_generics Clone for Call #type_generics
+		where
+			#( #all_args_type: Clone, )*
+		{
+			fn clone(&self) -> Self {
+				match self {
+					#(
+						Call::#fn_name { #( #args_name ),* } => Call::#fn_name {
+							#( #clone_arg ),*
+						},
+					)*
+					#ignore_arm
+				}
+			}
+		}
+
+		impl #impl_generics PartialEq for Call #type_generics
+		where
+			#( #all_args_type: PartialEq, )*
+		{
+			fn eq(&self, other: &Self) -> bool {
+				match (self, other) {
+					#(
+						(
+							Call::#fn_name { #( #args_name ),* },
+							Call::#fn_name { #( #args_name: #other_args_name ),* },
+						) => true #( #eq_arg )*,
+					)*
+					// This is unreachable when the pallet only has a single call.
+					#[allow(unreachable_patterns)]
+					_ => false,
+				}
+			}
+		}
+
+		impl #impl_generics Eq for Call #type_generics
+		where
+			#( #all_args_type: Eq, )*
+		{
+		}
+
+		#( #eq_assert )*
 	};
 
-	// Return the generated code.
-	dispatch_impl.into()
+	// This quote block implements `Encode` and `Decode` for the `Call` enum. A call is encoded as
+	// its call index, followed by each of its arguments in order.
+	let codec_impl = quote! {
+		impl #impl_generics Call #type_generics {
+			// The index of this call, which is used as the first byte when encoding the call.
+			pub fn call_index(&self) -> u8 {
+				match self {
+					#( Call::#fn_name { .. } => #call_index, )*
+					#ignore_arm
+				}
+			}
+
+			// The name of this call, which is the name of the function it calls.
+			pub fn call_name(&self) -> &'static str {
+				match self {
+					#( Call::#fn_name { .. } => stringify!(#fn_name), )*
+					#ignore_arm
+				}
+			}
+
+			// The names of all the calls of this pallet, in the order they are defined.
+			pub fn call_names() -> &'static [&'static str] {
+				&[ #( stringify!(#fn_name), )* ]
+			}
+		}
+
+		impl #impl_generics crate::support::Encode for Call #type_generics
+		where
+			#( #all_args_type: crate::support::Encode, )*
+		{
+			fn encode_to(&self, dest: &mut Vec<u8>) {
+				dest.push(self.call_index());
+				match self {
+					#(
+						Call::#fn_name { #( #args_name ),* } => {
+							#( #encode_arg )*
+						},
+					)*
+					#ignore_arm
+				}
+			}
+		}
+
+		impl #impl_generics crate::support::Decode for Call #type_generics
+		where
+			#( #all_args_type: crate::support::Decode, )*
+		{
+			fn decode(input: &mut &[u8]) -> Result<Self, &'static str> {
+				match <u8 as crate::support::Decode>::decode(input)? {
+					#(
+						#call_index => Ok(Call::#fn_name {
+							#( #decode_arg ),*
+						}),
+					)*
+					_ => Err("unknown call index"),
+				}
+			}
+		}
+	};
+
+	// This is a vector of the string version of each function name.
+	let fn_name_str = fn_name.iter().map(|name| name.to_string()).collect::<Vec<_>>();
+
+	// This quote block implements `FromStr` for the `Call` enum, so a call can be parsed from a
+	// string like `transfer to=bob amount=30`. Each argument is parsed using its own `FromStr`.
+	let parse_impl = quote! {
+		impl #impl_generics core::str::FromStr for Call #type_generics
+		where
+			#( #all_args_type: core::str::FromStr, )*
+		{
+			type Err = crate::support::ParseCallError;
+
+			fn from_str(s: &str) -> Result<Self, Self::Err> {
+				let (call_name, mut args) = crate::support::CallArgs::parse(s)?;
+				let call = match call_name.as_str() {
+					#(
+						#fn_name_str => Call::#fn_name {
+							#( #parse_arg ),*
+						},
+					)*
+					_ => return Err(crate::support::ParseCallError::UnknownCall(call_name)),
+				};
+				args.finish()?;
+				Ok(call)
+			}
+		}
+	};
+
+	// Return the updated `impl` block, and all the generated code.
+	quote! {
+		#item
+		#dispatch_impl
+		#traits_impl
+		#codec_impl
+		#parse_impl
+	}
+}
+
+// Whether a type mentions any of the `generics` of the pallet, like `T` in `T::AccountId`.
+fn mentions_generics(type_: &syn::Type, generics: &[syn::Ident]) -> bool {
+	fn mentions(tokens: proc_macro2::TokenStream, generics: &[syn::Ident]) -> bool {
+		tokens.into_iter().any(|token| match token {
+			TokenTree::Ident(ident) => generics.contains(&ident),
+			TokenTree::Group(group) => mentions(group.stream(), generics),
+			_ => false,
+		})
+	}
+	mentions(type_.to_token_stream(), generics)
+}
+
+// The name of an argument of the `other` call when comparing two calls, like `other_data` for
+// `data`. Any leading underscores are kept in front, so `_data` gives `_other_data`, which is still
+// snake case.
+fn other_name(name: &syn::Ident) -> syn::Ident {
+	let name = name.to_string();
+	let trimmed = name.trim_start_matches('_');
+	let underscores = &name[..name.len() - trimmed.len()];
+	quote::format_ident!("{}other_{}", underscores, trimmed)
+}
+
+// Build the code which uses each argument of each call, from the name and type of the argument.
+fn spanned_args(
+	methods: &[super::parse::CallVariantDef],
+	f: impl Fn(&syn::Ident, &syn::Type) -> proc_macro2::TokenStream,
+) -> Vec<Vec<proc_macro2::TokenStream>> {
+	methods
+		.iter()
+		.map(|method| method.args.iter().map(|(name, type_)| f(name, type_)).collect())
+		.collect()
 }
@@ -6,20 +6,20 @@ pub fn call(
 	_attr: proc_macro::TokenStream,
 	item: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-	// The final expanded code will be placed here.
-	// Since our macro only adds new code, our final product will contain all of our old code too,
-	// hence we clone `item`.
-	let mut finished = item.clone();
 	let item_mod = syn::parse_macro_input!(item as syn::Item);
 
 	// First we parse the call functions implemented for the pallet...
-	let generated: proc_macro::TokenStream = match parse::CallDef::try_from(item_mod.clone()) {
-		// ..then we generate our new code.
+	match parse::CallDef::try_from(item_mod.clone()) {
+		// ..then we generate our new code. Since we remove helper attributes like `#[call_index]`,
+		// the expanded code also includes the updated `impl` block.
 		Ok(def) => expand::expand_call(def).into(),
-		Err(e) => e.to_compile_error().into(),
-	};
-
-	// Add our generated code to the end, and return the final result.
-	finished.extend(generated);
-	return finished;
+		// If parsing fails, we return our old code along with the error, so that the compiler does
+		// not report extra errors about missing code.
+		Err(e) => {
+			let mut item_mod = item_mod;
+			parse::remove_helper_attrs(&mut item_mod);
+			let error = e.to_compile_error();
+			quote::quote!(#item_mod #error).into()
+		},
+	}
 }
@@ -5,15 +5,20 @@ use syn::spanned::Spanned;
 mod keyword {
 	syn::custom_keyword!(T);
 	syn::custom_keyword!(AccountId);
+	syn::custom_keyword!(skip);
 }
 
 /// This object will collect all the information we need to keep while parsing the callable
 /// functions.
 #[derive(Debug)]
 pub struct CallDef {
+	/// The original `impl` block, with our helper attributes like `#[call_index]` removed.
+	pub item: syn::ItemImpl,
 	/// This is the name of the pallet struct where the callable functions are implemented. We
 	/// mostly assume it is `Pallet`.
 	pub pallet_struct: syn::Ident,
+	/// The instance parameter of the pallet, if the pallet is instanced like `Pallet<T, I>`.
+	pub instance: Option<syn::Ident>,
 	/// This is a list of the callable functions exposed by this pallet. See `CallVariantDef`.
 	pub methods: Vec<CallVariantDef>,
 }
@@ -23,14 +28,25 @@ pub struct CallDef {
 pub struct CallVariantDef {
 	/// The function name.
 	pub name: syn::Ident,
+	/// The index of the call, used when encoding the call. This comes from `#[call_index(n)]`, or
+	/// the order of the function in the `impl` block if no call indices are given.
+	pub index: u8,
+	/// The `///` doc comments of the function, which we also place on the `Call` variant.
+	pub docs: Vec<syn::Attribute>,
+	/// Whether the function takes `runtime: &mut T` instead of `&mut self`, so it can use other
+	/// pallets in the runtime.
+	pub takes_runtime: bool,
 	/// Information on args of the function: `(name, type)`.
 	pub args: Vec<(syn::Ident, Box<syn::Type>)>,
+	/// The `where` clause of the function, like `T: GetPallet<Self>`, which we also need when
+	/// dispatching the call.
+	pub where_predicates: Vec<syn::WherePredicate>,
 }
 
 impl CallDef {
 	pub fn try_from(item: syn::Item) -> syn::Result<Self> {
 		// First we check that we are parsing an `impl`.
-		let item_impl = if let syn::Item::Impl(item) = item {
+		let mut item_impl = if let syn::Item::Impl(item) = item {
 			item
 		} else {
 			return Err(syn::Error::new(item.span(), "Invalid pallet::call, expected item impl"))
@@ -39,69 +55,302 @@ impl CallDef {
 		// Extract the name of the struct. We mostly assume it is `Pallet`, but we can handle it
 		// when it isn't.
 		let pallet_struct = match &*item_impl.self_ty {
-			syn::Type::Path(tp) => tp.path.segments.first().unwrap().ident.clone(),
-			_ => panic!("not supported tokens"),
+			syn::Type::Path(tp) if tp.qself.is_none() && tp.path.segments.len() == 1 =>
+				tp.path.segments[0].ident.clone(),
+			self_ty => {
+				let msg = "Invalid pallet::call, expected the pallet struct, like `Pallet<T>`";
+				return Err(syn::Error::new(self_ty.span(), msg))
+			},
 		};
 
+		// Find the instance parameter of the pallet, if it has one.
+		let instance = pallet_instance(&item_impl.self_ty)?;
+
+		// Take out the `#[call]` and `#[call(skip)]` markers of each function, since they are not
+		// real attributes.
+		let mut markers = vec![];
+		for item in item_impl.items.iter_mut() {
+			if let syn::ImplItem::Fn(method) = item {
+				markers.push(take_call_marker(&mut method.attrs)?);
+			}
+		}
+		// If any function is marked with `#[call]`, then only the marked functions are callable.
+		// Otherwise, every function is callable unless it is marked with `#[call(skip)]`.
+		let only_marked = markers.contains(&CallMarker::Call);
+
 		// Here is where we will store all the callable functions.
 		let mut methods = vec![];
-		for item in item_impl.items {
-			if let syn::ImplItem::Fn(method) = item {
-				// Here is where we will store all the args for each callable functions.
-				let mut args = vec![];
-
-				// First argument should be some variant of `self`.
-				match method.sig.inputs.first() {
-					Some(syn::FnArg::Receiver(_)) => {},
-					_ => {
-						let msg = "Invalid call, first argument must be a variant of self";
-						return Err(syn::Error::new(method.sig.span(), msg))
-					},
-				}
+		// Here is where we will store the explicit `#[call_index(n)]` of each function, if any.
+		let mut call_indices = vec![];
+		let fns = item_impl.items.iter_mut().filter_map(|item| match item {
+			syn::ImplItem::Fn(method) => Some(method),
+			_ => None,
+		});
+		for (method, marker) in fns.zip(markers) {
+			// Take out the `#[call_index(n)]` attribute, since it is not a real attribute.
+			let call_index = take_call_index(&mut method.attrs)?;
 
-				// The second argument should be the `caller: T::AccountId` argument.
-				match method.sig.inputs.iter().skip(1).next() {
-					Some(syn::FnArg::Typed(arg)) => {
-						// Here we specifically check that this argument is as we expect for
-						// `caller: T::AccountId`.
-						check_caller_arg(arg)?;
-					},
-					_ => {
-						let msg = "Invalid call, second argument should be `caller: T::AccountId`";
-						return Err(syn::Error::new(method.sig.span(), msg))
-					},
-				}
+			let is_callable = match marker {
+				CallMarker::Call => true,
+				CallMarker::Skip => false,
+				CallMarker::None => !only_marked,
+			};
 
-				let fn_name = method.sig.ident.clone();
-
-				// Parsing the rest of the args. Skipping 2 for `self` and `caller`.
-				for arg in method.sig.inputs.iter().skip(2) {
-					// All arguments should be typed.
-					let arg = if let syn::FnArg::Typed(arg) = arg {
-						arg
-					} else {
-						unreachable!("All args should be typed.");
-					};
-
-					// Extract the name of the argument.
-					let arg_ident = if let syn::Pat::Ident(pat) = &*arg.pat {
-						pat.ident.clone()
-					} else {
-						let msg = "Invalid pallet::call, argument must be ident";
-						return Err(syn::Error::new(arg.pat.span(), msg))
-					};
-
-					// Store the argument name and the argument type for generating code.
-					args.push((arg_ident, arg.ty.clone()));
+			// Functions which are not callable are simply helper functions, which we leave as is.
+			if !is_callable {
+				if let Some((_, span)) = call_index {
+					let msg = "Invalid pallet::call, `#[call_index]` is only allowed on callable \
+						functions";
+					return Err(syn::Error::new(span, msg))
 				}
+				continue
+			}
+			call_indices.push(call_index);
+
+			// Here is where we will store all the args for each callable functions.
+			let mut args = vec![];
+
+			// First argument should be some variant of `self`, or `runtime: &mut T`.
+			let takes_runtime = match method.sig.inputs.first() {
+				Some(syn::FnArg::Receiver(_)) => false,
+				Some(syn::FnArg::Typed(arg)) => {
+					check_runtime_arg(arg)?;
+					true
+				},
+				None => {
+					let msg =
+						"Invalid call, first argument must be a variant of self or `runtime: &mut T`";
+					return Err(syn::Error::new(method.sig.paren_token.span.join(), msg))
+				},
+			};
+
+			// The second argument should be the `caller: T::AccountId` argument.
+			match method.sig.inputs.iter().nth(1) {
+				Some(syn::FnArg::Typed(arg)) => {
+					// Here we specifically check that this argument is as we expect for
+					// `caller: T::AccountId`.
+					check_caller_arg(arg)?;
+				},
+				Some(arg) => {
+					let msg = "Invalid call, second argument should be `caller: T::AccountId`";
+					return Err(syn::Error::new(arg.span(), msg))
+				},
+				None => {
+					let msg = "Invalid call, second argument should be `caller: T::AccountId`";
+					return Err(syn::Error::new(method.sig.paren_token.span.join(), msg))
+				},
+			}
+
+			let fn_name = method.sig.ident.clone();
+			let docs = method
+				.attrs
+				.iter()
+				.filter(|attr| attr.path().is_ident("doc"))
+				.cloned()
+				.collect();
+
+			// Parsing the rest of the args. Skipping 2 for `self` (or `runtime`) and `caller`.
+			for arg in method.sig.inputs.iter().skip(2) {
+				// All arguments should be typed. Only the first argument can be `self`.
+				let arg = if let syn::FnArg::Typed(arg) = arg {
+					arg
+				} else {
+					let msg =
+						"Invalid pallet::call, only the first argument can be a variant of self";
+					return Err(syn::Error::new(arg.span(), msg))
+				};
 
-				// Store all the function name and the arg data for the function.
-				methods.push(CallVariantDef { name: fn_name, args });
+				// Extract the name of the argument.
+				let arg_ident = if let syn::Pat::Ident(pat) = &*arg.pat {
+					pat.ident.clone()
+				} else {
+					let msg = "Invalid pallet::call, argument must be ident";
+					return Err(syn::Error::new(arg.pat.span(), msg))
+				};
+
+				// Store the argument name and the argument type for generating code.
+				args.push((arg_ident, arg.ty.clone()));
 			}
+
+			// Store all the function name and the arg data for the function. The index is
+			// updated below, once we have seen all the functions.
+			let where_predicates = method
+				.sig
+				.generics
+				.where_clause
+				.iter()
+				.flat_map(|where_clause| where_clause.predicates.iter().cloned())
+				.collect();
+			methods.push(CallVariantDef {
+				name: fn_name,
+				index: 0,
+				docs,
+				takes_runtime,
+				args,
+				where_predicates,
+			});
 		}
 
+		// Assign the index of each call.
+		assign_call_indices(&mut methods, call_indices)?;
+
 		// Return all callable functions for this pallet.
-		Ok(Self { pallet_struct, methods })
+		Ok(Self { item: item_impl, pallet_struct, instance, methods })
+	}
+}
+
+/// Remove all of our helper attributes from the callable functions, without any checks. This is
+/// used to return the original code when parsing fails.
+pub fn remove_helper_attrs(item: &mut syn::Item) {
+	if let syn::Item::Impl(item_impl) = item {
+		for item in item_impl.items.iter_mut() {
+			if let syn::ImplItem::Fn(method) = item {
+				method
+					.attrs
+					.retain(|attr| !attr.path().is_ident("call_index") && !is_call_marker(attr));
+			}
+		}
+	}
+}
+
+/// Find the instance parameter of the pallet from the type the calls are implemented for. A normal
+/// pallet is implemented for `Pallet<T>`, and has no instance. An instanced pallet is implemented
+/// for `Pallet<T, I>`, where `I` is the instance.
+fn pallet_instance(self_ty: &syn::Type) -> syn::Result<Option<syn::Ident>> {
+	let syn::Type::Path(type_path) = self_ty else { return Ok(None) };
+	let syn::PathArguments::AngleBracketed(args) = &type_path.path.segments[0].arguments else {
+		return Ok(None)
+	};
+
+	let msg = "Invalid pallet::call, expected `Pallet<T>` or `Pallet<T, I>`";
+	match args.args.iter().collect::<Vec<_>>()[..] {
+		[_] => Ok(None),
+		[_, syn::GenericArgument::Type(syn::Type::Path(instance))]
+			if instance.qself.is_none() && instance.path.get_ident().is_some() =>
+			Ok(instance.path.get_ident().cloned()),
+		_ => Err(syn::Error::new(args.span(), msg)),
+	}
+}
+
+/// The markers which can be placed on a function to choose whether it is callable.
+#[derive(Debug, PartialEq)]
+enum CallMarker {
+	/// The function has no marker.
+	None,
+	/// The function is marked with `#[call]`, and is callable.
+	Call,
+	/// The function is marked with `#[call(skip)]`, and is only a helper function.
+	Skip,
+}
+
+/// Check if an attribute is our `#[call]` marker. We also accept `#[pallet::call]`, like the
+/// Polkadot SDK.
+fn is_call_marker(attr: &syn::Attribute) -> bool {
+	let path = attr.path();
+	path.is_ident("call") ||
+		(path.segments.len() == 2 &&
+			path.segments[0].ident == "pallet" &&
+			path.segments[1].ident == "call")
+}
+
+/// Remove the `#[call]` or `#[call(skip)]` marker from a list of attributes, and return which one
+/// it was.
+fn take_call_marker(attrs: &mut Vec<syn::Attribute>) -> syn::Result<CallMarker> {
+	let mut marker = CallMarker::None;
+	for attr in attrs.iter().filter(|attr| is_call_marker(attr)) {
+		if marker != CallMarker::None {
+			let msg = "Invalid pallet::call, only one `#[call]` marker is allowed per function";
+			return Err(syn::Error::new(attr.span(), msg))
+		}
+		marker = match &attr.meta {
+			syn::Meta::Path(_) => CallMarker::Call,
+			syn::Meta::List(list) => {
+				list.parse_args::<keyword::skip>()?;
+				CallMarker::Skip
+			},
+			syn::Meta::NameValue(_) => {
+				let msg = "Invalid pallet::call, expected `#[call]` or `#[call(skip)]`";
+				return Err(syn::Error::new(attr.span(), msg))
+			},
+		};
+	}
+	attrs.retain(|attr| !is_call_marker(attr));
+	Ok(marker)
+}
+
+/// Remove the `#[call_index(n)]` attribute from a list of attributes, and return the index along
+/// with the span of the attribute.
+fn take_call_index(
+	attrs: &mut Vec<syn::Attribute>,
+) -> syn::Result<Option<(u8, proc_macro2::Span)>> {
+	let mut call_index = None;
+	for attr in attrs.iter().filter(|attr| attr.path().is_ident("call_index")) {
+		if call_index.is_some() {
+			let msg = "Invalid pallet::call, only one `#[call_index]` is allowed per function";
+			return Err(syn::Error::new(attr.span(), msg))
+		}
+		let index = attr.parse_args::<syn::LitInt>()?.base10_parse::<u8>()?;
+		call_index = Some((index, attr.span()));
+	}
+	attrs.retain(|attr| !attr.path().is_ident("call_index"));
+	Ok(call_index)
+}
+
+/// Assign the index of each call. If no function uses `#[call_index(n)]`, we simply use the order
+/// of the functions. Otherwise, every function must have a unique `#[call_index(n)]`, so that
+/// reordering the functions never changes how calls are encoded.
+fn assign_call_indices(
+	methods: &mut [CallVariantDef],
+	call_indices: Vec<Option<(u8, proc_macro2::Span)>>,
+) -> syn::Result<()> {
+	if call_indices.iter().all(|index| index.is_none()) {
+		if methods.len() > u8::MAX as usize + 1 {
+			let msg = "Invalid pallet::call, too many calls";
+			return Err(syn::Error::new(methods[0].name.span(), msg))
+		}
+		methods.iter_mut().enumerate().for_each(|(i, method)| method.index = i as u8);
+		return Ok(())
+	}
+
+	let mut used = std::collections::BTreeMap::new();
+	for (method, call_index) in methods.iter_mut().zip(call_indices) {
+		let Some((index, span)) = call_index else {
+			let msg =
+				"Invalid pallet::call, missing `#[call_index(n)]`, which is required once any \
+				call uses it";
+			return Err(syn::Error::new(method.name.span(), msg))
+		};
+		if let Some(other) = used.insert(index, method.name.clone()) {
+			let msg =
+				format!("Invalid pallet::call, call index {index} is already used by `{other}`");
+			return Err(syn::Error::new(span, msg))
+		}
+		method.index = index;
+	}
+
+	Ok(())
+}
+
+/// Check the first arg of a call which does not take `self` is exactly: `runtime: &mut T`.
+///
+/// This is kept strict to keep the code simple.
+fn check_runtime_arg(arg: &syn::PatType) -> syn::Result<()> {
+	let msg = "Invalid call, first argument must be a variant of self or `runtime: &mut T`";
+
+	// This checks the arg name is `runtime` or `_runtime`.
+	match &*arg.pat {
+		syn::Pat::Ident(ident) if ident.ident == "runtime" || ident.ident == "_runtime" => {},
+		pat => return Err(syn::Error::new(pat.span(), msg)),
+	}
+
+	// This checks the type is `&mut T`.
+	match &*arg.ty {
+		syn::Type::Reference(reference)
+			if reference.mutability.is_some() &&
+				reference.lifetime.is_none() &&
+				matches!(&*reference.elem, syn::Type::Path(path) if path.path.is_ident("T")) =>
+			Ok(()),
+		ty => Err(syn::Error::new(ty.span(), msg)),
 	}
 }
 
@@ -122,10 +371,13 @@ pub fn check_caller_arg(arg: &syn::PatType) -> syn::Result<()> {
 	// This checks the arg name is `caller` or `_caller`.
 	if let syn::Pat::Ident(ident) = &*arg.pat {
 		// We also support the name as `_caller` for when the variable is unused.
-		if &ident.ident != "caller" && &ident.ident != "_caller" {
+		if ident.ident != "caller" && ident.ident != "_caller" {
 			let msg = "Invalid name for second parameter: expected `caller: T::AccountId`";
 			return Err(syn::Error::new(ident.span(), msg))
 		}
+	} else {
+		let msg = "Invalid pattern for second parameter: expected `caller: T::AccountId`";
+		return Err(syn::Error::new(arg.pat.span(), msg))
 	}
 
 	// This checks the type is `T::AccountId` with `CheckDispatchableFirstArg`
@@ -0,0 +1,127 @@
+use super::parse::ErrorDef;
+use quote::quote;
+
+/// See the `fn error` docs at the `lib.rs` of this crate for a high level definition.
+pub fn expand_error(def: ErrorDef) -> proc_macro2::TokenStream {
+	let ErrorDef { mut item, instance, variants } = def;
+
+	// The enum is generic over `T`, but none of our variants use it.
+	add_hidden_variant(&mut item);
+
+	// The generics of our `impl` blocks, and of the types we implement for. An instanced pallet is
+	// generic over both `T` and its instance `I`. Like the runtime, an instance must be `'static`
+	// so the `PalletInfo` can find the pallet by its `TypeId`.
+	let (impl_generics, type_generics) = match &instance {
+		Some(i) => (quote!(<T: Config<#i>, #i: 'static>), quote!(<T, #i>)),
+		None => (quote!(<T: Config>), quote!(<T>)),
+	};
+
+	let error_enum = &item.ident;
+
+	// This is a vector of all the error variant names.
+	let variant_name = variants.iter().map(|variant| &variant.name).collect::<Vec<_>>();
+	// This is a vector of the string version of each variant name.
+	let variant_name_str = variant_name.iter().map(|name| name.to_string()).collect::<Vec<_>>();
+	// This is a vector of the index of each variant, which is just the order it was defined in.
+	let variant_index = (0..variants.len()).map(|index| index as u8).collect::<Vec<_>>();
+	// This is a vector of the error messages, taken from the doc comments of each variant. If a
+	// variant has no docs, we simply use the variant name.
+	let variant_docs =
+		variants
+			.iter()
+			.map(|variant| {
+				if variant.docs.is_empty() {
+					variant.name.to_string()
+				} else {
+					variant.docs.clone()
+				}
+			})
+			.collect::<Vec<_>>();
+
+	// This quote block implements the helper functions for our `Error` enum, and the conversion
+	// into the runtime level `DispatchError`.
+	let error_impl = quote! {
+		impl #impl_generics #error_enum #type_generics {
+			/// The index of this error within the pallet.
+			pub fn index(&self) -> u8 {
+				match self {
+					#( Self::#variant_name => #variant_index, )*
+					Self::__Ignore(_, never) => match *never {},
+				}
+			}
+
+			/// The name of this error.
+			pub fn name(&self) -> &'static str {
+				match self {
+					#( Self::#variant_name => #variant_name_str, )*
+					Self::__Ignore(_, never) => match *never {},
+				}
+			}
+
+			/// The message of this error, taken from its doc comments.
+			pub fn message(&self) -> &'static str {
+				match self {
+					#( Self::#variant_name => #variant_docs, )*
+					Self::__Ignore(_, never) => match *never {},
+				}
+			}
+
+			/// The metadata of all the errors in this pallet.
+			pub fn metadata() -> Vec<crate::support::ErrorMetadata> {
+				vec![
+					#(
+						crate::support::ErrorMetadata {
+							name: #variant_name_str,
+							index: #variant_index,
+							docs: #variant_docs,
+						},
+					)*
+				]
+			}
+		}
+
+		impl #impl_generics core::fmt::Debug for #error_enum #type_generics {
+			fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+				f.write_str(self.name())
+			}
+		}
+
+		impl #impl_generics core::fmt::Display for #error_enum #type_generics {
+			fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+				f.write_str(self.message())
+			}
+		}
+
+		// Convert the pallet error into a runtime level error. The pallet index comes from the
+		// `PalletInfo` generated by our `runtime` macro. A pallet which is not part of a runtime has
+		// no index, so its errors are only described by their message.
+		impl #impl_generics From<#error_enum #type_generics> for crate::support::DispatchError {
+			fn from(error: #error_enum #type_generics) -> Self {
+				match <T::PalletInfo as crate::support::PalletInfo>::index::<Pallet #type_generics>() {
+					Some(index) => crate::support::DispatchError::Module(crate::support::ModuleError {
+						index,
+						error: error.index(),
+						message: error.message(),
+					}),
+					None => crate::support::DispatchError::Other(error.message()),
+				}
+			}
+		}
+	};
+
+	// Return the updated enum, and all our generated code.
+	quote! {
+		#item
+		#error_impl
+	}
+}
+
+/// Add a hidden variant to the enum which uses all of its type parameters, like `T`. This variant
+/// can never be constructed since it contains `Infallible`.
+pub fn add_hidden_variant(item: &mut syn::ItemEnum) {
+	let type_params = item.generics.type_params().map(|param| &param.ident).collect::<Vec<_>>();
+	item.variants.push(syn::parse_quote! {
+		#[doc(hidden)]
+		__Ignore(core::marker::PhantomData<( #( #type_params, )* )>, core::convert::Infallible)
+	});
+}
@@ -0,0 +1,31 @@
+pub mod expand;
+pub mod parse;
+
+/// See the `fn error` docs at the `lib.rs` of this crate for a high level definition.
+pub fn error(
+	_attr: proc_macro::TokenStream,
+	item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+	let item_enum = syn::parse_macro_input!(item as syn::Item);
+
+	// First we parse the `Error` enum...
+	match parse::ErrorDef::try_from(item_enum.clone()) {
+		// ..then we generate our new code. Since we add a hidden variant to the `Error` enum, the
+		// expanded code also includes the updated enum.
+		Ok(def) => expand::expand_error(def).into(),
+		// If parsing fails, we return our old code along with the error, so that the compiler does
+		// not report extra errors about missing code.
+		Err(e) => {
+			let mut item_enum = item_enum;
+			// We still add our hidden variant, so the compiler does not complain that `T` is
+			// unused. This is not possible when the enum has discriminants.
+			if let syn::Item::Enum(item) = &mut item_enum {
+				if item.variants.iter().all(|variant| variant.discriminant.is_none()) {
+					expand::add_hidden_variant(item);
+				}
+			}
+			let error = e.to_compile_error();
+			quote::quote!(#item_enum #error).into()
+		},
+	}
+}
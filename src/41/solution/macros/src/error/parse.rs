@@ -0,0 +1,105 @@
+use syn::spanned::Spanned;
+
+/// This object will collect all the information we need to keep while parsing the `Error` enum.
+#[derive(Debug)]
+pub struct ErrorDef {
+	/// The original enum, which we will output again with an extra hidden variant.
+	pub item: syn::ItemEnum,
+	/// The instance parameter of the enum, if the pallet is instanced like `Error<T, I>`.
+	pub instance: Option<syn::Ident>,
+	/// The list of errors in this enum. See `ErrorVariantDef`.
+	pub variants: Vec<ErrorVariantDef>,
+}
+
+/// This is the metadata we keep about each variant of the `Error` enum.
+#[derive(Debug)]
+pub struct ErrorVariantDef {
+	/// The variant name.
+	pub name: syn::Ident,
+	/// The doc comments of the variant, joined into a single line. This is used as the error
+	/// message.
+	pub docs: String,
+}
+
+impl ErrorDef {
+	pub fn try_from(item: syn::Item) -> syn::Result<Self> {
+		// First we check that we are parsing an `enum`.
+		let item_enum = if let syn::Item::Enum(item) = item {
+			item
+		} else {
+			return Err(syn::Error::new(item.span(), "Invalid pallet::error, expected item enum"))
+		};
+
+		// We expect the error enum to be generic over exactly `T`, just like our `Pallet<T>`, or
+		// over `T` and `I` for an instanced `Pallet<T, I>`.
+		let type_params = item_enum
+			.generics
+			.params
+			.iter()
+			.map(|param| match param {
+				syn::GenericParam::Type(ty) if ty.bounds.is_empty() => Some(&ty.ident),
+				_ => None,
+			})
+			.collect::<Vec<_>>();
+		let instance = match type_params[..] {
+			[Some(t)] if t == "T" => None,
+			[Some(t), Some(i)] if t == "T" => Some(i.clone()),
+			_ => {
+				let msg = "Invalid pallet::error, expected `Error<T>` or `Error<T, I>`";
+				let span = if type_params.is_empty() {
+					item_enum.ident.span()
+				} else {
+					item_enum.generics.span()
+				};
+				return Err(syn::Error::new(span, msg))
+			},
+		};
+
+		// Here is where we will store all the error variants.
+		let mut variants = vec![];
+		for variant in item_enum.variants.iter() {
+			// We keep errors simple: they are only used to identify what went wrong.
+			if !matches!(variant.fields, syn::Fields::Unit) {
+				let msg = "Invalid pallet::error, expected unit variant";
+				return Err(syn::Error::new(variant.fields.span(), msg))
+			}
+
+			if let Some((_, discriminant)) = &variant.discriminant {
+				let msg =
+					"Invalid pallet::error, unexpected discriminant, errors are indexed in order";
+				return Err(syn::Error::new(discriminant.span(), msg))
+			}
+
+			variants.push(ErrorVariantDef {
+				name: variant.ident.clone(),
+				docs: get_docs(&variant.attrs),
+			});
+		}
+
+		// The error index is stored as a `u8`, so the last variant can have index 255.
+		if variants.len() > u8::MAX as usize + 1 {
+			let msg = "Invalid pallet::error, too many variants, at most 256 are allowed";
+			return Err(syn::Error::new(item_enum.span(), msg))
+		}
+
+		Ok(Self { item: item_enum, instance, variants })
+	}
+}
+
+/// Extract the `///` doc comments from a list of attributes, and join them into a single line.
+pub fn get_docs(attrs: &[syn::Attribute]) -> String {
+	attrs
+		.iter()
+		.filter_map(|attr| match &attr.meta {
+			// A `/// some docs` comment is just sugar for `#[doc = " some docs"]`.
+			syn::Meta::NameValue(meta) if meta.path.is_ident("doc") => match &meta.value {
+				syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(s), .. }) => Some(s.value()),
+				_ => None,
+			},
+			_ => None,
+		})
+		.map(|line| line.trim().to_string())
+		.filter(|line| !line.is_empty())
+		.collect::<Vec<_>>()
+		.join(" ")
+}
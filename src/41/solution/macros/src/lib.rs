@@ -1,6 +1,47 @@
 mod call;
+mod error;
 mod runtime;
 
+/// Expand the callable functions of a pallet.
+///
+/// Every function in the `impl` block is a callable function, and must take `&mut self` and
+/// `caller: T::AccountId` as its first two arguments.
+///
+/// A function which needs other pallets, like the system pallet, can instead take the whole runtime
+/// as `runtime: &mut T`, and reach each pallet through `support::GetPallet`. Any `where` clause of
+/// a callable function, like `where T: GetPallet<Self>`, is also required when dispatching calls.
+///
+/// Helper functions can be placed in the same `impl` block and left untouched:
+/// - a function marked with `#[call(skip)]` is not callable.
+/// - if any function is marked with `#[call]` (or `#[pallet::call]`), then only the marked
+///   functions are callable.
+///
+/// These markers are removed from the final code.
+///
+/// An instanced pallet, which can be included in the runtime more than once, implements its calls
+/// for `Pallet<T, I>` with `T: Config<I>`. Its `Call` enum is then `Call<T, I = ()>`.
+///
+/// This generates:
+/// - `enum Call` - an enum with a variant for each callable function, containing all of the
+///   arguments of the function except `self` and `caller`. The `///` docs of each function are kept
+///   on its variant.
+/// - implementations of `Debug`, `Clone`, `PartialEq` and `Eq` for `Call`. These only require the
+///   argument types, like `T::AccountId`, to implement each trait, rather than `T` itself.
+/// - implements the trait `support::DispatchWithRuntime` for the pallet, which routes each `Call`
+///   to the appropriate function, given the runtime.
+/// - implements the trait `support::Dispatch` for the pallet, if every callable function takes
+///   `&mut self`, so the pallet can dispatch calls on its own.
+/// - `fn call_index()` and implementations of `support::Encode` and `support::Decode` for `Call`. A
+///   call is encoded as its call index followed by its arguments.
+/// - `fn call_name()` - which returns the name of the function a `Call` is for, and `fn
+///   call_names()` - which returns the names of all the callable functions.
+/// - an implementation of `FromStr` for `Call`, which parses a call from a string like `transfer
+///   to=bob amount=30` using `support::CallArgs`. Each argument is parsed with its own `FromStr`.
+///
+/// By default, the call index of a function is the order in which it is defined. To keep encoded
+/// calls stable when functions are reordered, each function can be given an explicit index with
+/// `#[call_index(n)]`. Once any function uses `#[call_index(n)]`, all of them must, and every index
+/// must be unique.
 #[proc_macro_attribute]
 pub fn call(
 	attr: proc_macro::TokenStream,
@@ -9,22 +50,86 @@ pub fn call(
 	call::call(attr, item)
 }
 
+/// Expand the `Error` enum of a pallet.
+///
+/// The enum must be named `Error<T>`, or `Error<T, I>` for an instanced pallet, and only contain
+/// unit variants. The `///` docs of each variant are used as the error message.
+///
+/// This adds a hidden variant to the enum which uses `T`, and generates:
+/// - `fn index()`, `fn name()` and `fn message()` - which describe a single error. The index of an
+///   error is the order in which it is defined.
+/// - `fn metadata()` - which returns the `support::ErrorMetadata` of every error in the pallet.
+/// - implementations of `Debug` and `Display`, where `Display` prints the error message.
+/// - a conversion into `support::DispatchError`, which includes the index of the pallet as provided
+///   by the `PalletInfo` generated by `#[macros::runtime]`.
+#[proc_macro_attribute]
+pub fn error(
+	attr: proc_macro::TokenStream,
+	item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+	error::error(attr, item)
+}
+
 /// Expand the `Runtime` definition.
 ///
+/// The runtime struct can have any name, and its fields can be in any order. Each field should be
+/// a pallet type with its module, like `balances::Pallet<Self>`, since we find the pallet's `Call`
+/// and `Error` types in that module. An instanced pallet includes its instance in its type, like
+/// `balances::Pallet<Self, support::Instance1>`, and gets its own variant in `RuntimeCall`. The
+/// macro accepts some optional arguments:
+/// - `system = ...` - the name of the field holding the system pallet. Defaults to `system`.
+/// - `block = ...` - the path to the block type executed by the runtime. Defaults to
+///   `types::Block`.
+/// - `try_state` - check the invariants of every pallet after each block, failing the block if any
+///   are broken. Disabled by default.
+///
+/// For example: `#[macros::runtime(system = frame_system, block = my_types::Block)]`.
+///
 /// This generates function implementations on `Runtime`:
 /// - `fn new()` - which generates a new instance of the runtime, by instantiating all the pallets
 ///   included in the runtime.
 /// - `fn execute_block()` - which handles basic logic for executing a block of extrinsics. It does
 ///   basic actions like incrementing the block number and checking the block to be executed has a
-///   valid block number.
+///   valid block number. It then calls `support::Hooks::on_initialize` with the whole runtime for
+///   every pallet other than the system pallet, so every such pallet must implement
+///   `support::Hooks<Runtime, BlockNumber>`.
+/// - `fn try_state()` - which calls `support::Hooks::try_state` on every pallet other than the
+///   system pallet, to check their invariants.
 ///
-/// This also generates code needed for dispatching calls to the pallets:
-/// - Note: For simplicity, we assume that the system pallet is not callable.
+/// This also generates code needed for dispatching calls to the pallets. The system pallet is
+/// callable like any other pallet, so it must define its calls using `#[macros::call]`:
 /// - `enum RuntimeCall` - an "outer"-enum representing the accumulation of all possible calls to
-///   all pallets. The system pallet is not included.
-/// - implements the trait `support::Dispatch` to dispatch calls to the appropriate pallet. Basic
-///   logic like incrementing the nonce of the user is included in the generated code. The system
-///   pallet is not included.
+///   all pallets, including the system pallet. This derives `Debug`, `Clone`, `PartialEq` and `Eq`.
+/// - implements the trait `support::Dispatch` to dispatch calls to the appropriate pallet, using
+///   the `support::DispatchWithRuntime` implementation of each pallet. Basic logic like
+///   incrementing the nonce of the user is included in the generated code. Calls which are not
+///   allowed by the `BaseCallFilter` of the system pallet fail with `DispatchError::CallFiltered`,
+///   without being dispatched. The filter is given the runtime, so it can read the state of a
+///   pallet.
+/// - `fn pallet_index()` and implementations of `support::Encode` and `support::Decode` for
+///   `RuntimeCall`. A runtime call is encoded as the pallet index followed by the pallet call.
+/// - conversions between `RuntimeCall` and the `Call` of each pallet: `From` to build a runtime
+///   call, and `TryFrom` and `support::IsSubType` to get the pallet call back.
+/// - `fn pallet_name()` and `fn call_name()` - which return the name of the pallet and call a
+///   `RuntimeCall` is for. These are also available through the trait `support::CallMetadata`,
+///   which also lists the names of the calls of every pallet in the runtime.
+/// - an implementation of `FromStr` for `RuntimeCall`, which parses a call from a string like
+///   `balances.transfer to=bob amount=30`. This requires the arguments of every call in the runtime
+///   to implement `FromStr`.
+///
+/// By default, the index of a pallet is the order in which it is included in the `Runtime` struct.
+/// To keep encoded calls stable when pallets are reordered, each field can be given an explicit
+/// index with `#[pallet_index(n)]`. Once any field uses `#[pallet_index(n)]`, all of them must,
+/// including `system`, and every index must be unique.
+///
+/// Finally, this generates information about the pallets included in the runtime:
+/// - implements the trait `support::PalletInfo`, which allows pallets to find their own index and
+///   name in the runtime.
+/// - implements the trait `support::GetPallet` for every pallet, including the system pallet, which
+///   allows generic code like a call filter to access a pallet in the runtime.
+/// - `fn metadata()` - which returns the `support::RuntimeMetadata` describing every pallet and its
+///   errors. We assume every pallet other than the system pallet defines its `Error` enum using
+///   `#[macros::error]`.
 #[proc_macro_attribute]
 pub fn runtime(
 	attr: proc_macro::TokenStream,
@@ -1,14 +1,58 @@
-use super::parse::RuntimeDef;
-use quote::quote;
+use super::parse::{PalletDef, RuntimeDef};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::spanned::Spanned;
 
 /// See the `fn runtime` docs at the `lib.rs` of this crate for a high level definition.
 pub fn expand_runtime(def: RuntimeDef) -> proc_macro2::TokenStream {
-	let RuntimeDef { runtime_struct, pallets } = def;
+	let RuntimeDef { item, runtime_struct, block, try_state, system, pallets } = def;
+
+	// The name of the system pallet field, its type, its module and its index.
+	let system_name = &system.name;
+	let system_type = &system.ty;
+	let system_module = &system.module;
+	let system_index = system.index;
+	let system_name_str = system_name.to_string();
 
 	// This is a vector of all the pallet names, not including system.
-	let pallet_names = pallets.iter().map(|(name, _)| name.clone()).collect::<Vec<_>>();
+	let pallet_names = pallets.iter().map(|pallet| pallet.name.clone()).collect::<Vec<_>>();
 	// This is a vector of all the pallet types, not including system.
-	let pallet_types = pallets.iter().map(|(_, type_)| type_.clone()).collect::<Vec<_>>();
+	let pallet_types = pallets.iter().map(|pallet| pallet.ty.clone()).collect::<Vec<_>>();
+	// This is a vector of the module of each pallet, not including system.
+	let pallet_modules = pallets.iter().map(|pallet| pallet.module.clone()).collect::<Vec<_>>();
+	// This is a vector of the generics we use for the `Call` and `Error` types of each pallet, not
+	// including system. An instanced pallet also includes its instance, like
+	// `<Runtime, support::Instance1>`.
+	let pallet_generics = pallets
+		.iter()
+		.map(|pallet| generics(pallet, &runtime_struct))
+		.collect::<Vec<_>>();
+	// This is a vector of the index of each pallet, not including system.
+	let pallet_indices = pallets.iter().map(|pallet| pallet.index).collect::<Vec<_>>();
+
+	// The system pallet is callable like any other pallet, so we use these vectors, which include
+	// system first, for everything to do with `RuntimeCall`.
+	let call_pallets = core::iter::once(&system).chain(pallets.iter()).collect::<Vec<_>>();
+	let call_names = call_pallets.iter().map(|pallet| pallet.name.clone()).collect::<Vec<_>>();
+	let call_modules = call_pallets.iter().map(|pallet| pallet.module.clone()).collect::<Vec<_>>();
+	let call_generics = call_pallets
+		.iter()
+		.map(|pallet| generics(pallet, &runtime_struct))
+		.collect::<Vec<_>>();
+	let call_indices = call_pallets.iter().map(|pallet| pallet.index).collect::<Vec<_>>();
+	let call_names_str = call_names.iter().map(|name| name.to_string()).collect::<Vec<_>>();
+	// This is a vector of the types of all the pallets, including system. Since these are used
+	// outside of the `Runtime` struct, we replace `Self` with the name of the runtime.
+	let call_types = call_pallets
+		.iter()
+		.map(|pallet| replace_self(pallet.ty.to_token_stream(), &runtime_struct))
+		.collect::<Vec<_>>();
+
+	// When `try_state` is enabled, we check the invariants of every pallet after each block.
+	let try_state_check = try_state.then(|| {
+		quote! {
+			self.try_state()?;
+		}
+	});
 
 	// This quote block implements functions on the `Runtime` struct.
 	let runtime_impl = quote! {
@@ -17,7 +61,7 @@ pub fn expand_runtime(def: RuntimeDef) -> proc_macro2::TokenStream {
 			fn new() -> Self {
 				Self {
 					// Since system is not included in the list of pallets, we manually add it here.
-					system: <system::Pallet::<Self>>::new(),
+					#system_name: <#system_type>::new(),
 					#(
 						#pallet_names: <#pallet_types>::new()
 					),*
@@ -25,25 +69,50 @@ pub fn expand_runtime(def: RuntimeDef) -> proc_macro2::TokenStream {
 			}
 
 			// Execute a block of extrinsics. Increments the block number.
-			fn execute_block(&mut self, block: types::Block) -> crate::support::DispatchResult {
-				self.system.inc_block_number();
-				if block.header.block_number != self.system.block_number() {
-					return Err(&"block number does not match what is expected")
-				}
-				for (i, support::Extrinsic { caller, call }) in block.extrinsics.into_iter().enumerate() {
-					self.system.inc_nonce(&caller);
-					let _res = self.dispatch(caller, call).map_err(|e| {
+			fn execute_block(&mut self, block: #block) -> crate::support::DispatchResult {
+				self.#system_name.inc_block_number();
+				if block.header.block_number != self.#system_name.block_number() {
+					return Err("block number does not match what is expected".into())
+				}
+				// Let each pallet run its own logic at the start of the block.
+				let block_number = self.#system_name.block_number();
+				#( <#pallet_types as crate::support::Hooks<Self, _>>::on_initialize(self, block_number); )*
+				for (i, crate::support::Extrinsic { caller, call }) in block.extrinsics.into_iter().enumerate() {
+					self.#system_name.inc_nonce(&caller);
+					let (pallet_name, call_name) = (call.pallet_name(), call.call_name());
+					let _res = crate::support::Dispatch::dispatch(self, caller, call).map_err(|e| {
 						eprintln!(
-							"Extrinsic Error\n\tBlock Number: {}\n\tExtrinsic Number: {}\n\tError: {}",
-							block.header.block_number, i, e
+							"Extrinsic Error\n\tBlock Number: {}\n\tExtrinsic Number: {}\n\tCall: {}.{}\n\tError: {}",
+							block.header.block_number, i, pallet_name, call_name, e
 						)
 					});
 				}
+				#try_state_check
+				Ok(())
+			}
+
+			// Check the invariants of every pallet, not including system.
+			#[allow(dead_code)]
+			fn try_state(&self) -> crate::support::DispatchResult {
+				let block_number = self.#system_name.block_number();
+				#( <#pallet_types as crate::support::Hooks<Self, _>>::try_state(&self.#pallet_names, block_number)?; )*
 				Ok(())
 			}
 		}
 	};
 
+	// This is a vector of the calls which route a `RuntimeCall` to each pallet. Each call goes
+	// through a function which requires `DispatchWithRuntime`, and uses the span of the pallet
+	// type, so a pallet which does not implement it gives a single error pointing to the right
+	// field.
+	let dispatch_calls = call_types
+		.iter()
+		.zip(&call_pallets)
+		.map(|(type_, pallet)| {
+			quote_spanned!(pallet.ty.span() => #runtime_struct::dispatch_pallet::<#type_, _>(self, caller, call))
+		})
+		.collect::<Vec<_>>();
+
 	// This quote block implements the `RuntimeCall` enum and implements the `Dispatch` trait.
 	let dispatch_impl = quote! {
 		// These are all the calls which are exposed to the world.
@@ -51,12 +120,31 @@ pub fn expand_runtime(def: RuntimeDef) -> proc_macro2::TokenStream {
 		//
 		// The parsed function names will be `snake_case`, and that will show up in the enum.
 		#[allow(non_camel_case_types)]
+		#[derive(Debug, Clone, PartialEq, Eq)]
 		pub enum RuntimeCall {
-			#( #pallet_names(#pallet_names::Call<#runtime_struct>) ),*
+			#( #call_names(#call_modules::Call #call_generics) ),*
+		}
+
+		impl #runtime_struct {
+			// Dispatch the call of a single pallet, with access to the whole runtime.
+			fn dispatch_pallet<P, Call>(
+				&mut self,
+				caller: <Self as crate::support::Dispatch>::Caller,
+				call: Call,
+			) -> crate::support::DispatchResult
+			where
+				P: crate::support::DispatchWithRuntime<
+					Self,
+					Caller = <Self as crate::support::Dispatch>::Caller,
+					Call = Call,
+				>,
+			{
+				P::dispatch_with_runtime(self, caller, call)
+			}
 		}
 
 		impl crate::support::Dispatch for #runtime_struct {
-			type Caller = <Runtime as system::Config>::AccountId;
+			type Caller = <#runtime_struct as #system_module::Config>::AccountId;
 			type Call = RuntimeCall;
 			// Dispatch a call on behalf of a caller. Increments the caller's nonce.
 			//
@@ -68,12 +156,20 @@ pub fn expand_runtime(def: RuntimeDef) -> proc_macro2::TokenStream {
 				caller: Self::Caller,
 				runtime_call: Self::Call,
 			) -> crate::support::DispatchResult {
+				// Calls which are not allowed by the `BaseCallFilter` are never routed to a pallet.
+				if !<
+					<#runtime_struct as #system_module::Config>::BaseCallFilter
+						as crate::support::CallFilter<#runtime_struct, RuntimeCall>
+				>::allows(self, &runtime_call) {
+					return Err(crate::support::DispatchError::CallFiltered)
+				}
+
 				// This match statement will allow us to correctly route `RuntimeCall`s
 				// to the appropriate pallet level call.
 				match runtime_call {
 					#(
-						RuntimeCall::#pallet_names(call) => {
-							self.#pallet_names.dispatch(caller, call)?;
+						RuntimeCall::#call_names(call) => {
+							#dispatch_calls?;
 						}
 					),*
 				}
@@ -82,10 +178,243 @@ pub fn expand_runtime(def: RuntimeDef) -> proc_macro2::TokenStream {
 		}
 	};
 
+	// This is a vector of the string version of each pallet name.
+	let pallet_names_str = pallet_names.iter().map(|name| name.to_string()).collect::<Vec<_>>();
+
+	// This quote block implements `PalletInfo` for the `Runtime`, and the runtime metadata.
+	let pallet_info_impl = quote! {
+		impl crate::support::PalletInfo for #runtime_struct {
+			fn index<P: 'static>() -> Option<u8> {
+				let type_id = core::any::TypeId::of::<P>();
+				if type_id == core::any::TypeId::of::<#system_type>() {
+					return Some(#system_index)
+				}
+				#(
+					if type_id == core::any::TypeId::of::<#pallet_types>() {
+						return Some(#pallet_indices)
+					}
+				)*
+				None
+			}
+
+			fn name<P: 'static>() -> Option<&'static str> {
+				let type_id = core::any::TypeId::of::<P>();
+				if type_id == core::any::TypeId::of::<#system_type>() {
+					return Some(#system_name_str)
+				}
+				#(
+					if type_id == core::any::TypeId::of::<#pallet_types>() {
+						return Some(#pallet_names_str)
+					}
+				)*
+				None
+			}
+		}
+
+		impl #runtime_struct {
+			// Describe all the pallets in this runtime, and the errors they can return.
+			pub fn metadata() -> crate::support::RuntimeMetadata {
+				crate::support::RuntimeMetadata {
+					pallets: vec![
+						crate::support::PalletMetadata {
+							name: #system_name_str,
+							index: #system_index,
+							errors: vec![],
+						},
+						#(
+							crate::support::PalletMetadata {
+								name: #pallet_names_str,
+								index: #pallet_indices,
+								errors: #pallet_modules::Error::#pallet_generics::metadata(),
+							}
+						),*
+					],
+				}
+			}
+		}
+	};
+
+	// This quote block implements `Encode` and `Decode` for the `RuntimeCall` enum. A runtime call
+	// is encoded as the pallet index, followed by the encoded pallet call.
+	let codec_impl = quote! {
+		impl RuntimeCall {
+			// The index of the pallet this call is routed to, which is used as the first byte when
+			// encoding the call.
+			pub fn pallet_index(&self) -> u8 {
+				match self {
+					#( RuntimeCall::#call_names(_) => #call_indices, )*
+				}
+			}
+		}
+
+		impl crate::support::Encode for RuntimeCall {
+			fn encode_to(&self, dest: &mut Vec<u8>) {
+				dest.push(self.pallet_index());
+				match self {
+					#( RuntimeCall::#call_names(call) => call.encode_to(dest), )*
+				}
+			}
+		}
+
+		impl crate::support::Decode for RuntimeCall {
+			fn decode(input: &mut &[u8]) -> Result<Self, &'static str> {
+				match <u8 as crate::support::Decode>::decode(input)? {
+					#(
+						#call_indices => Ok(RuntimeCall::#call_names(
+							crate::support::Decode::decode(input)?
+						)),
+					)*
+					_ => Err("unknown pallet index"),
+				}
+			}
+		}
+	};
+
+	// This quote block implements conversions between the `RuntimeCall` and the `Call` of each
+	// pallet, and functions to find out which pallet and call a `RuntimeCall` is for.
+	let conversions_impl = quote! {
+		impl RuntimeCall {
+			// The name of the pallet this call is routed to, which is the name of its field in the
+			// runtime.
+			pub fn pallet_name(&self) -> &'static str {
+				match self {
+					#( RuntimeCall::#call_names(_) => #call_names_str, )*
+				}
+			}
+
+			// The name of the call within its pallet.
+			pub fn call_name(&self) -> &'static str {
+				match self {
+					#( RuntimeCall::#call_names(call) => call.call_name(), )*
+				}
+			}
+		}
+
+		impl crate::support::CallMetadata for RuntimeCall {
+			fn pallet_name(&self) -> &'static str {
+				RuntimeCall::pallet_name(self)
+			}
+
+			fn call_name(&self) -> &'static str {
+				RuntimeCall::call_name(self)
+			}
+
+			fn call_names(pallet: &str) -> Option<&'static [&'static str]> {
+				match pallet {
+					#( #call_names_str => Some(<#call_modules::Call #call_generics>::call_names()), )*
+					_ => None,
+				}
+			}
+		}
+
+		#(
+			impl From<#call_modules::Call #call_generics> for RuntimeCall {
+				fn from(call: #call_modules::Call #call_generics) -> Self {
+					RuntimeCall::#call_names(call)
+				}
+			}
+
+			// Get back the pallet call from a `RuntimeCall`. If the runtime call is for another
+			// pallet, we return it unchanged as the error.
+			impl TryFrom<RuntimeCall> for #call_modules::Call #call_generics {
+				type Error = RuntimeCall;
+
+				fn try_from(runtime_call: RuntimeCall) -> Result<Self, Self::Error> {
+					match runtime_call {
+						RuntimeCall::#call_names(call) => Ok(call),
+						// This is unreachable when the runtime only has the system pallet.
+						#[allow(unreachable_patterns)]
+						other => Err(other),
+					}
+				}
+			}
+
+			impl crate::support::IsSubType<#call_modules::Call #call_generics> for RuntimeCall {
+				fn is_sub_type(&self) -> Option<&#call_modules::Call #call_generics> {
+					match self {
+						RuntimeCall::#call_names(call) => Some(call),
+						// This is unreachable when the runtime only has the system pallet.
+						#[allow(unreachable_patterns)]
+						_ => None,
+					}
+				}
+			}
+		)*
+	};
+
+	// This quote block implements `GetPallet` for the `Runtime`, for every pallet it includes.
+	let get_pallet_impl = quote! {
+		#(
+			impl crate::support::GetPallet<#call_types> for #runtime_struct {
+				fn pallet(&self) -> &#call_types {
+					&self.#call_names
+				}
+
+				fn pallet_mut(&mut self) -> &mut #call_types {
+					&mut self.#call_names
+				}
+			}
+		)*
+	};
+
+	// This quote block implements `FromStr` for the `RuntimeCall` enum, so a call can be parsed
+	// from a string like `balances.transfer to=bob amount=30`. The rest of the string after the
+	// pallet name is parsed by the `FromStr` of that pallet's `Call`.
+	let parse_impl = quote! {
+		impl core::str::FromStr for RuntimeCall {
+			type Err = crate::support::ParseCallError;
+
+			fn from_str(s: &str) -> Result<Self, Self::Err> {
+				let (pallet_name, call) = s.trim_start().split_once('.').ok_or(
+					crate::support::ParseCallError::InvalidFormat("expected a call like `pallet.call`"),
+				)?;
+				match pallet_name {
+					#( #call_names_str => Ok(RuntimeCall::#call_names(call.parse()?)), )*
+					_ => Err(crate::support::ParseCallError::UnknownPallet(pallet_name.to_string())),
+				}
+			}
+		}
+	};
+
 	// We combine and return all the generated code.
 	quote! {
+		#item
 		#dispatch_impl
+		#codec_impl
+		#conversions_impl
+		#parse_impl
 		#runtime_impl
+		#pallet_info_impl
+		#get_pallet_impl
 	}
-	.into()
+}
+
+/// Replace every `Self` in some tokens with the name of the runtime struct.
+fn replace_self(
+	tokens: proc_macro2::TokenStream,
+	runtime_struct: &syn::Ident,
+) -> proc_macro2::TokenStream {
+	tokens
+		.into_iter()
+		.map(|token| match token {
+			proc_macro2::TokenTree::Ident(ident) if ident == "Self" =>
+				proc_macro2::TokenTree::Ident(runtime_struct.clone()),
+			proc_macro2::TokenTree::Group(group) => {
+				let mut new_group = proc_macro2::Group::new(
+					group.delimiter(),
+					replace_self(group.stream(), runtime_struct),
+				);
+				new_group.set_span(group.span());
+				proc_macro2::TokenTree::Group(new_group)
+			},
+			token => token,
+		})
+		.collect()
+}
+
+/// The generics we use for the `Call` and `Error` types of a pallet, like `<Runtime>`. An instanced
+/// pallet also includes its instance, like `<Runtime, support::Instance1>`.
+fn generics(pallet: &PalletDef, runtime_struct: &syn::Ident) -> proc_macro2::TokenStream {
+	let instance = pallet.instance.iter();
+	quote!(<#runtime_struct #(, #instance)*>)
 }
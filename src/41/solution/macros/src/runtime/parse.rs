@@ -3,65 +3,264 @@ use syn::spanned::Spanned;
 /// This object will collect all the information we need to keep while parsing the `Runtime` struct.
 #[derive(Debug)]
 pub struct RuntimeDef {
-	/// This is the name of the struct used by the user. We mostly assume it is `Runtime`.
+	/// The original `Runtime` struct, with our helper attributes like `#[pallet_index]` removed.
+	pub item: syn::ItemStruct,
+	/// This is the name of the struct used by the user. This is usually `Runtime`, but it can be
+	/// anything, like `TestRuntime`.
 	pub runtime_struct: syn::Ident,
+	/// The path to the block type which is executed by the runtime.
+	pub block: syn::Path,
+	/// Whether to check the invariants of every pallet after each block.
+	pub try_state: bool,
+	/// The system pallet, which we find using the `system` argument of the macro.
+	pub system: PalletDef,
 	/// This is the list of pallets included in the `Runtime` struct. We omit `system` from this
-	/// list, but during parsing we check that system exists.
-	pub pallets: Vec<(syn::Ident, syn::Type)>,
+	/// list, but during parsing we check that system exists. See `PalletDef`.
+	pub pallets: Vec<PalletDef>,
+}
+
+/// This is the metadata we keep about each pallet in the `Runtime` struct.
+#[derive(Debug)]
+pub struct PalletDef {
+	/// The name of the field.
+	pub name: syn::Ident,
+	/// The type of the pallet, for example `balances::Pallet<Self>`.
+	pub ty: syn::Type,
+	/// The path to the module of the pallet, taken from the type of the pallet. For example
+	/// `balances` for `balances::Pallet<Self>`. This is where we find the `Call` and `Error` of
+	/// the pallet.
+	pub module: syn::Path,
+	/// The instance of the pallet, if it is instanced. For example `support::Instance1` for
+	/// `balances::Pallet<Self, support::Instance1>`.
+	pub instance: Option<syn::Type>,
+	/// The index of the pallet, used when encoding calls. This comes from `#[pallet_index(n)]`, or
+	/// the order of the field in the `Runtime` struct if no pallet indices are given.
+	pub index: u8,
+}
+
+/// The arguments given to the macro, like `#[macros::runtime(system = frame_system)]`.
+#[derive(Debug)]
+pub struct RuntimeArgs {
+	/// The name of the field in the `Runtime` struct which holds the system pallet. Defaults to
+	/// `system`.
+	pub system: syn::Ident,
+	/// The path to the block type which is executed by the runtime. Defaults to `types::Block`.
+	pub block: syn::Path,
+	/// Whether to check the invariants of every pallet after each block, enabled by the
+	/// `try_state` argument. Defaults to `false`.
+	pub try_state: bool,
+}
+
+impl RuntimeArgs {
+	pub fn parse(attr: proc_macro2::TokenStream) -> syn::Result<Self> {
+		let mut system: Option<syn::Ident> = None;
+		let mut block: Option<syn::Path> = None;
+		let mut try_state = false;
+
+		let parser = syn::meta::parser(|meta| {
+			if meta.path.is_ident("system") {
+				if system.is_some() {
+					return Err(meta.error("Invalid runtime, `system` is given more than once"))
+				}
+				system = Some(meta.value()?.parse()?);
+			} else if meta.path.is_ident("block") {
+				if block.is_some() {
+					return Err(meta.error("Invalid runtime, `block` is given more than once"))
+				}
+				block = Some(meta.value()?.parse()?);
+			} else if meta.path.is_ident("try_state") {
+				if try_state {
+					return Err(meta.error("Invalid runtime, `try_state` is given more than once"))
+				}
+				try_state = true;
+			} else {
+				return Err(meta.error(
+					"Invalid runtime, expected `system = ...`, `block = ...` or `try_state`",
+				))
+			}
+			Ok(())
+		});
+		syn::parse::Parser::parse2(parser, attr)?;
+
+		Ok(Self {
+			system: system.unwrap_or_else(|| quote::format_ident!("system")),
+			block: block.unwrap_or_else(|| syn::parse_quote!(types::Block)),
+			try_state,
+		})
+	}
 }
 
 impl RuntimeDef {
-	pub fn try_from(item: syn::Item) -> syn::Result<Self> {
+	pub fn try_from(args: RuntimeArgs, item: syn::Item) -> syn::Result<Self> {
 		// First we check that we are parsing a `struct`.
-		let item_struct = if let syn::Item::Struct(item) = item {
+		let mut item_struct = if let syn::Item::Struct(item) = item {
 			item
 		} else {
 			return Err(syn::Error::new(item.span(), "Invalid runtime, expected item struct"))
 		};
 
-		// We check that the `Runtime` includes the `system` pallet as the first item.
-		check_system(&item_struct)?;
+		// We keep the runtime simple: it is a plain struct with named fields, one for each pallet.
+		if !item_struct.generics.params.is_empty() {
+			let msg = "Invalid runtime, the runtime struct cannot be generic";
+			return Err(syn::Error::new(item_struct.generics.span(), msg))
+		}
+		if let syn::Fields::Unnamed(fields) = &item_struct.fields {
+			let msg = "Invalid runtime, expected a struct with named fields, found a tuple struct";
+			return Err(syn::Error::new(fields.span(), msg))
+		}
+
+		// We check that the `Runtime` includes the `system` pallet, and find where it is.
+		let system_position = find_system(&item_struct, &args.system)?;
 
-		let runtime_struct = item_struct.ident;
+		let runtime_struct = item_struct.ident.clone();
+
+		// Take out the `#[pallet_index(n)]` attribute of each field, since it is not a real
+		// attribute.
+		let mut pallet_indices = vec![];
+		for field in item_struct.fields.iter_mut() {
+			pallet_indices.push(take_pallet_index(&mut field.attrs)?);
+		}
+		let indices = assign_pallet_indices(&item_struct, pallet_indices)?;
 
 		// Here is where we will store a list of all the pallets.
 		let mut pallets = vec![];
-		// We skip `system`, which we ensure is the first field in `check_system`.
-		for field in item_struct.fields.into_iter().skip(1) {
-			if let Some(ident) = field.ident {
-				pallets.push((ident, field.ty))
-			}
+		for (field, index) in item_struct.fields.iter().zip(indices) {
+			let Some(name) = field.ident.clone() else {
+				return Err(syn::Error::new(field.span(), "Invalid runtime, expected a named field"))
+			};
+			let module = pallet_module(&field.ty)?;
+			let instance = pallet_instance(&field.ty);
+			pallets.push(PalletDef { name, ty: field.ty.clone(), module, instance, index });
 		}
+		// We keep `system` separate from the rest of the pallets.
+		let system = pallets.remove(system_position);
 
-		Ok(Self { runtime_struct, pallets })
+		Ok(Self {
+			item: item_struct,
+			runtime_struct,
+			block: args.block,
+			try_state: args.try_state,
+			system,
+			pallets,
+		})
 	}
 }
 
-/// This function checks that the `system` pallet is the first pallet included in the `Runtime`
-/// struct. We make many assumptions about the `system` pallet in order to keep these macros simple.
-/// For example, we assume that the system pallet has no callable functions, and that it contains
-/// specific functions like incrementing the block number and a user's nonce.
-///
-/// You can consider these macros to be tightly coupled to the logic of the `system` pallet.
-fn check_system(item_struct: &syn::ItemStruct) -> syn::Result<()> {
-	// Extract the name of the first field in the `Runtime` struct.
-	let first_field_name = if let Some(first_field) = item_struct.fields.iter().next() {
-		if let Some(field_name) = &first_field.ident {
-			field_name.to_string()
-		} else {
-			let msg = "first field is expected to have the name system";
+/// Remove all of our helper attributes from the `Runtime` struct, without any checks. This is used
+/// to return the original code when parsing fails.
+pub fn remove_helper_attrs(item: &mut syn::Item) {
+	if let syn::Item::Struct(item_struct) = item {
+		for field in item_struct.fields.iter_mut() {
+			field.attrs.retain(|attr| !attr.path().is_ident("pallet_index"));
+		}
+	}
+}
+
+/// Remove the `#[pallet_index(n)]` attribute from a list of attributes, and return the index along
+/// with the span of the attribute.
+fn take_pallet_index(
+	attrs: &mut Vec<syn::Attribute>,
+) -> syn::Result<Option<(u8, proc_macro2::Span)>> {
+	let mut pallet_index = None;
+	for attr in attrs.iter().filter(|attr| attr.path().is_ident("pallet_index")) {
+		if pallet_index.is_some() {
+			let msg = "Invalid runtime, only one `#[pallet_index]` is allowed per pallet";
+			return Err(syn::Error::new(attr.span(), msg))
+		}
+		let index = attr.parse_args::<syn::LitInt>()?.base10_parse::<u8>()?;
+		pallet_index = Some((index, attr.span()));
+	}
+	attrs.retain(|attr| !attr.path().is_ident("pallet_index"));
+	Ok(pallet_index)
+}
+
+/// Find the index of each field in the `Runtime` struct. If no field uses `#[pallet_index(n)]`, we
+/// simply use the order of the fields. Otherwise, every field must have a unique
+/// `#[pallet_index(n)]`, so that reordering the pallets never changes how calls are encoded.
+fn assign_pallet_indices(
+	item_struct: &syn::ItemStruct,
+	pallet_indices: Vec<Option<(u8, proc_macro2::Span)>>,
+) -> syn::Result<Vec<u8>> {
+	if pallet_indices.iter().all(|index| index.is_none()) {
+		if pallet_indices.len() > u8::MAX as usize + 1 {
+			let msg = "Invalid runtime, too many pallets";
 			return Err(syn::Error::new(item_struct.span(), msg))
 		}
-	} else {
-		let msg = "runtime struct is expected to have fields";
-		return Err(syn::Error::new(item_struct.span(), msg))
+		return Ok((0..pallet_indices.len()).map(|i| i as u8).collect())
+	}
+
+	let mut used = std::collections::BTreeMap::new();
+	let mut indices = vec![];
+	for (field, pallet_index) in item_struct.fields.iter().zip(pallet_indices) {
+		let Some((index, span)) = pallet_index else {
+			let msg = "Invalid runtime, missing `#[pallet_index(n)]`, which is required once any \
+				pallet uses it";
+			return Err(syn::Error::new(field.span(), msg))
+		};
+		if let Some(other) = used.insert(index, field.ident.clone()) {
+			let other = other.map(|ident| ident.to_string()).unwrap_or_default();
+			let msg = format!("Invalid runtime, pallet index {index} is already used by `{other}`");
+			return Err(syn::Error::new(span, msg))
+		}
+		indices.push(index);
+	}
+
+	Ok(indices)
+}
+
+/// Find the path to the module of a pallet from its type. For example, the module of
+/// `balances::Pallet<Self>` is `balances`.
+fn pallet_module(ty: &syn::Type) -> syn::Result<syn::Path> {
+	let msg =
+		"Invalid runtime, expected a pallet type with its module, like `balances::Pallet<Self>`";
+	let syn::Type::Path(type_path) = ty else { return Err(syn::Error::new(ty.span(), msg)) };
+	if type_path.qself.is_some() || type_path.path.segments.len() < 2 {
+		return Err(syn::Error::new(ty.span(), msg))
+	}
+
+	let mut module = type_path.path.clone();
+	module.segments.pop();
+	// Remove the trailing `::` left over from the last segment.
+	module.segments.pop_punct();
+	Ok(module)
+}
+
+/// Find the instance of a pallet from its type. For example, the instance of
+/// `balances::Pallet<Self, support::Instance1>` is `support::Instance1`, and
+/// `balances::Pallet<Self>` has no instance.
+fn pallet_instance(ty: &syn::Type) -> Option<syn::Type> {
+	let syn::Type::Path(type_path) = ty else { return None };
+	let syn::PathArguments::AngleBracketed(args) = &type_path.path.segments.last()?.arguments
+	else {
+		return None
 	};
+	match args.args.iter().nth(1) {
+		Some(syn::GenericArgument::Type(instance)) => Some(instance.clone()),
+		_ => None,
+	}
+}
 
-	// Check if the first field is named "system"
-	if first_field_name != "system" {
-		let msg = "first field is expected to be named system";
-		return Err(syn::Error::new(item_struct.span(), msg))
+/// This function finds the position of the `system` pallet in the `Runtime` struct, using the name
+/// of the field given by the `system` argument of the macro. We make many assumptions about the
+/// `system` pallet in order to keep these macros simple. For example, we assume that the system
+/// pallet contains specific functions like incrementing the block number and a user's nonce.
+///
+/// You can consider these macros to be tightly coupled to the logic of the `system` pallet.
+fn find_system(item_struct: &syn::ItemStruct, system: &syn::Ident) -> syn::Result<usize> {
+	if item_struct.fields.is_empty() {
+		let msg = "runtime struct is expected to have fields";
+		return Err(syn::Error::new(item_struct.ident.span(), msg))
 	}
 
-	Ok(())
+	item_struct
+		.fields
+		.iter()
+		.position(|field| field.ident.as_ref() == Some(system))
+		.ok_or_else(|| {
+			let msg = format!(
+				"runtime struct is expected to have a field named `{system}` for the system \
+				pallet, use `#[macros::runtime(system = ...)]` to choose another field"
+			);
+			syn::Error::new(item_struct.ident.span(), msg)
+		})
 }
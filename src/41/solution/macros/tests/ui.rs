@@ -0,0 +1,14 @@
+// UI tests for our macros. Each file in `tests/ui/pass` should compile and run successfully. Every
+// other file in `tests/ui` should fail to compile with the error message in the matching `.stderr`
+// file.
+//
+// To update the `.stderr` files after changing an error message, run:
+// `TRYBUILD=overwrite cargo test -p macros`
+#[test]
+fn ui() {
+	let t = trybuild::TestCases::new();
+	t.pass("tests/ui/pass/*.rs");
+	t.compile_fail("tests/ui/call/*.rs");
+	t.compile_fail("tests/ui/runtime/*.rs");
+	t.compile_fail("tests/ui/error/*.rs");
+}
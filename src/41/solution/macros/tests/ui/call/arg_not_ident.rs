@@ -0,0 +1,23 @@
+#![allow(unused)]
+
+#[path = "../../../../src/support.rs"]
+mod support;
+
+use support::DispatchResult;
+
+pub trait Config {
+	type AccountId;
+}
+
+pub struct Pallet<T: Config> {
+	_config: core::marker::PhantomData<T>,
+}
+
+#[macros::call]
+impl<T: Config> Pallet<T> {
+	pub fn transfer(&mut self, caller: T::AccountId, (to, amount): (u32, u32)) -> DispatchResult {
+		Ok(())
+	}
+}
+
+fn main() {}
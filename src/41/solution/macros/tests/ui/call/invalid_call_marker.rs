@@ -0,0 +1,24 @@
+#![allow(unused)]
+
+#[path = "../../../../src/support.rs"]
+mod support;
+
+use support::DispatchResult;
+
+pub trait Config {
+	type AccountId;
+}
+
+pub struct Pallet<T: Config> {
+	_config: core::marker::PhantomData<T>,
+}
+
+#[macros::call]
+impl<T: Config> Pallet<T> {
+	#[call(ignore)]
+	pub fn transfer(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+}
+
+fn main() {}
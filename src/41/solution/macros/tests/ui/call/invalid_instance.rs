@@ -0,0 +1,23 @@
+#![allow(unused)]
+
+#[path = "../../../../src/support.rs"]
+mod support;
+
+use support::DispatchResult;
+
+pub trait Config<I, J> {
+	type AccountId;
+}
+
+pub struct Pallet<T: Config<I, J>, I, J> {
+	_config: core::marker::PhantomData<(T, I, J)>,
+}
+
+#[macros::call]
+impl<T: Config<I, J>, I, J> Pallet<T, I, J> {
+	pub fn transfer(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+}
+
+fn main() {}
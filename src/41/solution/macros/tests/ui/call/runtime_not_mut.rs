@@ -0,0 +1,24 @@
+#![allow(unused)]
+
+#[path = "../../../../src/support.rs"]
+mod support;
+
+use support::DispatchResult;
+
+pub trait Config {
+	type AccountId;
+}
+
+pub struct Pallet<T: Config> {
+	_config: core::marker::PhantomData<T>,
+}
+
+// The runtime must be given as `&mut T`, so the call can change it.
+#[macros::call]
+impl<T: Config> Pallet<T> {
+	pub fn transfer(runtime: &T, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+}
+
+fn main() {}
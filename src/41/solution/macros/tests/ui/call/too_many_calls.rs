@@ -0,0 +1,1048 @@
+#![allow(unused)]
+
+#[path = "../../../../src/support.rs"]
+mod support;
+
+use support::DispatchResult;
+
+pub trait Config {
+	type AccountId;
+}
+
+pub struct Pallet<T: Config> {
+	_config: core::marker::PhantomData<T>,
+}
+
+// A call index is a `u8`, so a pallet can have at most 256 calls.
+#[macros::call]
+impl<T: Config> Pallet<T> {
+	pub fn call_0(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_1(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_2(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_3(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_4(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_5(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_6(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_7(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_8(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_9(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_10(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_11(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_12(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_13(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_14(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_15(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_16(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_17(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_18(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_19(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_20(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_21(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_22(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_23(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_24(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_25(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_26(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_27(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_28(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_29(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_30(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_31(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_32(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_33(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_34(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_35(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_36(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_37(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_38(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_39(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_40(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_41(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_42(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_43(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_44(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_45(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_46(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_47(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_48(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_49(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_50(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_51(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_52(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_53(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_54(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_55(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_56(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_57(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_58(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_59(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_60(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_61(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_62(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_63(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_64(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_65(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_66(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_67(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_68(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_69(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_70(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_71(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_72(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_73(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_74(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_75(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_76(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_77(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_78(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_79(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_80(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_81(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_82(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_83(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_84(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_85(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_86(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_87(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_88(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_89(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_90(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_91(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_92(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_93(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_94(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_95(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_96(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_97(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_98(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_99(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_100(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_101(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_102(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_103(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_104(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_105(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_106(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_107(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_108(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_109(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_110(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_111(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_112(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_113(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_114(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_115(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_116(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_117(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_118(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_119(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_120(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_121(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_122(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_123(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_124(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_125(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_126(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_127(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_128(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_129(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_130(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_131(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_132(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_133(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_134(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_135(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_136(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_137(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_138(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_139(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_140(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_141(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_142(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_143(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_144(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_145(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_146(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_147(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_148(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_149(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_150(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_151(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_152(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_153(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_154(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_155(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_156(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_157(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_158(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_159(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_160(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_161(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_162(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_163(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_164(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_165(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_166(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_167(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_168(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_169(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_170(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_171(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_172(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_173(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_174(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_175(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_176(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_177(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_178(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_179(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_180(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_181(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_182(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_183(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_184(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_185(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_186(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_187(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_188(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_189(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_190(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_191(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_192(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_193(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_194(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_195(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_196(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_197(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_198(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_199(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_200(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_201(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_202(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_203(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_204(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_205(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_206(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_207(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_208(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_209(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_210(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_211(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_212(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_213(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_214(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_215(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_216(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_217(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_218(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_219(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_220(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_221(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_222(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_223(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_224(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_225(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_226(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_227(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_228(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_229(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_230(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_231(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_232(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_233(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_234(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_235(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_236(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_237(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_238(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_239(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_240(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_241(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_242(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_243(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_244(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_245(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_246(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_247(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_248(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_249(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_250(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_251(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_252(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_253(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_254(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_255(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	pub fn call_256(&mut self, caller: T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+}
+
+fn main() {}
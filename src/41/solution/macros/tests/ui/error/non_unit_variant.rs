@@ -0,0 +1,9 @@
+#![allow(unused)]
+
+#[macros::error]
+pub enum Error<T> {
+	/// Not enough funds.
+	InsufficientBalance(u32),
+}
+
+fn main() {}
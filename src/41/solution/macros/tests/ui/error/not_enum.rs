@@ -0,0 +1,8 @@
+#![allow(unused)]
+
+#[macros::error]
+pub struct Error<T> {
+	_config: core::marker::PhantomData<T>,
+}
+
+fn main() {}
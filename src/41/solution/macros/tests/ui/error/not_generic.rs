@@ -0,0 +1,9 @@
+#![allow(unused)]
+
+#[macros::error]
+pub enum Error {
+	/// Not enough funds.
+	InsufficientBalance,
+}
+
+fn main() {}
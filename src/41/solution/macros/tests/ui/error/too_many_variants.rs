@@ -0,0 +1,522 @@
+#![allow(unused)]
+
+// An error index is a `u8`, so a pallet can have at most 256 errors.
+#[macros::error]
+pub enum Error<T> {
+	/// Error number 0.
+	Error0,
+	/// Error number 1.
+	Error1,
+	/// Error number 2.
+	Error2,
+	/// Error number 3.
+	Error3,
+	/// Error number 4.
+	Error4,
+	/// Error number 5.
+	Error5,
+	/// Error number 6.
+	Error6,
+	/// Error number 7.
+	Error7,
+	/// Error number 8.
+	Error8,
+	/// Error number 9.
+	Error9,
+	/// Error number 10.
+	Error10,
+	/// Error number 11.
+	Error11,
+	/// Error number 12.
+	Error12,
+	/// Error number 13.
+	Error13,
+	/// Error number 14.
+	Error14,
+	/// Error number 15.
+	Error15,
+	/// Error number 16.
+	Error16,
+	/// Error number 17.
+	Error17,
+	/// Error number 18.
+	Error18,
+	/// Error number 19.
+	Error19,
+	/// Error number 20.
+	Error20,
+	/// Error number 21.
+	Error21,
+	/// Error number 22.
+	Error22,
+	/// Error number 23.
+	Error23,
+	/// Error number 24.
+	Error24,
+	/// Error number 25.
+	Error25,
+	/// Error number 26.
+	Error26,
+	/// Error number 27.
+	Error27,
+	/// Error number 28.
+	Error28,
+	/// Error number 29.
+	Error29,
+	/// Error number 30.
+	Error30,
+	/// Error number 31.
+	Error31,
+	/// Error number 32.
+	Error32,
+	/// Error number 33.
+	Error33,
+	/// Error number 34.
+	Error34,
+	/// Error number 35.
+	Error35,
+	/// Error number 36.
+	Error36,
+	/// Error number 37.
+	Error37,
+	/// Error number 38.
+	Error38,
+	/// Error number 39.
+	Error39,
+	/// Error number 40.
+	Error40,
+	/// Error number 41.
+	Error41,
+	/// Error number 42.
+	Error42,
+	/// Error number 43.
+	Error43,
+	/// Error number 44.
+	Error44,
+	/// Error number 45.
+	Error45,
+	/// Error number 46.
+	Error46,
+	/// Error number 47.
+	Error47,
+	/// Error number 48.
+	Error48,
+	/// Error number 49.
+	Error49,
+	/// Error number 50.
+	Error50,
+	/// Error number 51.
+	Error51,
+	/// Error number 52.
+	Error52,
+	/// Error number 53.
+	Error53,
+	/// Error number 54.
+	Error54,
+	/// Error number 55.
+	Error55,
+	/// Error number 56.
+	Error56,
+	/// Error number 57.
+	Error57,
+	/// Error number 58.
+	Error58,
+	/// Error number 59.
+	Error59,
+	/// Error number 60.
+	Error60,
+	/// Error number 61.
+	Error61,
+	/// Error number 62.
+	Error62,
+	/// Error number 63.
+	Error63,
+	/// Error number 64.
+	Error64,
+	/// Error number 65.
+	Error65,
+	/// Error number 66.
+	Error66,
+	/// Error number 67.
+	Error67,
+	/// Error number 68.
+	Error68,
+	/// Error number 69.
+	Error69,
+	/// Error number 70.
+	Error70,
+	/// Error number 71.
+	Error71,
+	/// Error number 72.
+	Error72,
+	/// Error number 73.
+	Error73,
+	/// Error number 74.
+	Error74,
+	/// Error number 75.
+	Error75,
+	/// Error number 76.
+	Error76,
+	/// Error number 77.
+	Error77,
+	/// Error number 78.
+	Error78,
+	/// Error number 79.
+	Error79,
+	/// Error number 80.
+	Error80,
+	/// Error number 81.
+	Error81,
+	/// Error number 82.
+	Error82,
+	/// Error number 83.
+	Error83,
+	/// Error number 84.
+	Error84,
+	/// Error number 85.
+	Error85,
+	/// Error number 86.
+	Error86,
+	/// Error number 87.
+	Error87,
+	/// Error number 88.
+	Error88,
+	/// Error number 89.
+	Error89,
+	/// Error number 90.
+	Error90,
+	/// Error number 91.
+	Error91,
+	/// Error number 92.
+	Error92,
+	/// Error number 93.
+	Error93,
+	/// Error number 94.
+	Error94,
+	/// Error number 95.
+	Error95,
+	/// Error number 96.
+	Error96,
+	/// Error number 97.
+	Error97,
+	/// Error number 98.
+	Error98,
+	/// Error number 99.
+	Error99,
+	/// Error number 100.
+	Error100,
+	/// Error number 101.
+	Error101,
+	/// Error number 102.
+	Error102,
+	/// Error number 103.
+	Error103,
+	/// Error number 104.
+	Error104,
+	/// Error number 105.
+	Error105,
+	/// Error number 106.
+	Error106,
+	/// Error number 107.
+	Error107,
+	/// Error number 108.
+	Error108,
+	/// Error number 109.
+	Error109,
+	/// Error number 110.
+	Error110,
+	/// Error number 111.
+	Error111,
+	/// Error number 112.
+	Error112,
+	/// Error number 113.
+	Error113,
+	/// Error number 114.
+	Error114,
+	/// Error number 115.
+	Error115,
+	/// Error number 116.
+	Error116,
+	/// Error number 117.
+	Error117,
+	/// Error number 118.
+	Error118,
+	/// Error number 119.
+	Error119,
+	/// Error number 120.
+	Error120,
+	/// Error number 121.
+	Error121,
+	/// Error number 122.
+	Error122,
+	/// Error number 123.
+	Error123,
+	/// Error number 124.
+	Error124,
+	/// Error number 125.
+	Error125,
+	/// Error number 126.
+	Error126,
+	/// Error number 127.
+	Error127,
+	/// Error number 128.
+	Error128,
+	/// Error number 129.
+	Error129,
+	/// Error number 130.
+	Error130,
+	/// Error number 131.
+	Error131,
+	/// Error number 132.
+	Error132,
+	/// Error number 133.
+	Error133,
+	/// Error number 134.
+	Error134,
+	/// Error number 135.
+	Error135,
+	/// Error number 136.
+	Error136,
+	/// Error number 137.
+	Error137,
+	/// Error number 138.
+	Error138,
+	/// Error number 139.
+	Error139,
+	/// Error number 140.
+	Error140,
+	/// Error number 141.
+	Error141,
+	/// Error number 142.
+	Error142,
+	/// Error number 143.
+	Error143,
+	/// Error number 144.
+	Error144,
+	/// Error number 145.
+	Error145,
+	/// Error number 146.
+	Error146,
+	/// Error number 147.
+	Error147,
+	/// Error number 148.
+	Error148,
+	/// Error number 149.
+	Error149,
+	/// Error number 150.
+	Error150,
+	/// Error number 151.
+	Error151,
+	/// Error number 152.
+	Error152,
+	/// Error number 153.
+	Error153,
+	/// Error number 154.
+	Error154,
+	/// Error number 155.
+	Error155,
+	/// Error number 156.
+	Error156,
+	/// Error number 157.
+	Error157,
+	/// Error number 158.
+	Error158,
+	/// Error number 159.
+	Error159,
+	/// Error number 160.
+	Error160,
+	/// Error number 161.
+	Error161,
+	/// Error number 162.
+	Error162,
+	/// Error number 163.
+	Error163,
+	/// Error number 164.
+	Error164,
+	/// Error number 165.
+	Error165,
+	/// Error number 166.
+	Error166,
+	/// Error number 167.
+	Error167,
+	/// Error number 168.
+	Error168,
+	/// Error number 169.
+	Error169,
+	/// Error number 170.
+	Error170,
+	/// Error number 171.
+	Error171,
+	/// Error number 172.
+	Error172,
+	/// Error number 173.
+	Error173,
+	/// Error number 174.
+	Error174,
+	/// Error number 175.
+	Error175,
+	/// Error number 176.
+	Error176,
+	/// Error number 177.
+	Error177,
+	/// Error number 178.
+	Error178,
+	/// Error number 179.
+	Error179,
+	/// Error number 180.
+	Error180,
+	/// Error number 181.
+	Error181,
+	/// Error number 182.
+	Error182,
+	/// Error number 183.
+	Error183,
+	/// Error number 184.
+	Error184,
+	/// Error number 185.
+	Error185,
+	/// Error number 186.
+	Error186,
+	/// Error number 187.
+	Error187,
+	/// Error number 188.
+	Error188,
+	/// Error number 189.
+	Error189,
+	/// Error number 190.
+	Error190,
+	/// Error number 191.
+	Error191,
+	/// Error number 192.
+	Error192,
+	/// Error number 193.
+	Error193,
+	/// Error number 194.
+	Error194,
+	/// Error number 195.
+	Error195,
+	/// Error number 196.
+	Error196,
+	/// Error number 197.
+	Error197,
+	/// Error number 198.
+	Error198,
+	/// Error number 199.
+	Error199,
+	/// Error number 200.
+	Error200,
+	/// Error number 201.
+	Error201,
+	/// Error number 202.
+	Error202,
+	/// Error number 203.
+	Error203,
+	/// Error number 204.
+	Error204,
+	/// Error number 205.
+	Error205,
+	/// Error number 206.
+	Error206,
+	/// Error number 207.
+	Error207,
+	/// Error number 208.
+	Error208,
+	/// Error number 209.
+	Error209,
+	/// Error number 210.
+	Error210,
+	/// Error number 211.
+	Error211,
+	/// Error number 212.
+	Error212,
+	/// Error number 213.
+	Error213,
+	/// Error number 214.
+	Error214,
+	/// Error number 215.
+	Error215,
+	/// Error number 216.
+	Error216,
+	/// Error number 217.
+	Error217,
+	/// Error number 218.
+	Error218,
+	/// Error number 219.
+	Error219,
+	/// Error number 220.
+	Error220,
+	/// Error number 221.
+	Error221,
+	/// Error number 222.
+	Error222,
+	/// Error number 223.
+	Error223,
+	/// Error number 224.
+	Error224,
+	/// Error number 225.
+	Error225,
+	/// Error number 226.
+	Error226,
+	/// Error number 227.
+	Error227,
+	/// Error number 228.
+	Error228,
+	/// Error number 229.
+	Error229,
+	/// Error number 230.
+	Error230,
+	/// Error number 231.
+	Error231,
+	/// Error number 232.
+	Error232,
+	/// Error number 233.
+	Error233,
+	/// Error number 234.
+	Error234,
+	/// Error number 235.
+	Error235,
+	/// Error number 236.
+	Error236,
+	/// Error number 237.
+	Error237,
+	/// Error number 238.
+	Error238,
+	/// Error number 239.
+	Error239,
+	/// Error number 240.
+	Error240,
+	/// Error number 241.
+	Error241,
+	/// Error number 242.
+	Error242,
+	/// Error number 243.
+	Error243,
+	/// Error number 244.
+	Error244,
+	/// Error number 245.
+	Error245,
+	/// Error number 246.
+	Error246,
+	/// Error number 247.
+	Error247,
+	/// Error number 248.
+	Error248,
+	/// Error number 249.
+	Error249,
+	/// Error number 250.
+	Error250,
+	/// Error number 251.
+	Error251,
+	/// Error number 252.
+	Error252,
+	/// Error number 253.
+	Error253,
+	/// Error number 254.
+	Error254,
+	/// Error number 255.
+	Error255,
+	/// Error number 256.
+	Error256,
+}
+
+fn main() {}
@@ -0,0 +1,148 @@
+#![allow(unused)]
+
+#[path = "../../../../src/support.rs"]
+mod support;
+
+// A minimal system pallet, with just what the runtime macro needs.
+mod frame_system {
+	pub trait Config: 'static {
+		type AccountId;
+		type PalletInfo: crate::support::PalletInfo;
+		type RuntimeCall;
+		type BaseCallFilter: crate::support::CallFilter<Self, Self::RuntimeCall>;
+	}
+
+	pub struct Pallet<T: Config> {
+		block_number: u32,
+		_config: core::marker::PhantomData<T>,
+	}
+
+	impl<T: Config> Pallet<T> {
+		pub fn new() -> Self {
+			Self { block_number: 0, _config: core::marker::PhantomData }
+		}
+
+		pub fn block_number(&self) -> u32 {
+			self.block_number
+		}
+
+		pub fn inc_block_number(&mut self) {
+			self.block_number += 1;
+		}
+
+		pub fn inc_nonce(&mut self, who: &T::AccountId) {}
+	}
+
+	// The system pallet must be callable, even if it has no calls.
+	#[macros::call]
+	impl<T: Config> Pallet<T> {}
+}
+
+mod my_types {
+	pub type Extrinsic = crate::support::Extrinsic<String, crate::RuntimeCall>;
+	pub type Header = crate::support::Header<u32>;
+	pub type Block = crate::support::Block<Header, Extrinsic>;
+}
+
+mod vote {
+	use crate::support::DispatchResult;
+
+	pub trait Config: crate::frame_system::Config {}
+
+	pub struct Pallet<T: Config> {
+		_config: core::marker::PhantomData<T>,
+	}
+
+	impl<T: Config> Pallet<T> {
+		pub fn new() -> Self {
+			Self { _config: core::marker::PhantomData }
+		}
+	}
+
+	impl<T: Config, R, B> crate::support::Hooks<R, B> for Pallet<T> {}
+
+	#[macros::error]
+	pub enum Error<T> {}
+
+	#[macros::call]
+	impl<T: Config> Pallet<T> {
+		pub fn vote(&mut self, caller: T::AccountId, candidate: T::AccountId) -> DispatchResult {
+			Ok(())
+		}
+	}
+}
+
+mod balances {
+	use crate::support::DispatchResult;
+
+	pub trait Config: crate::frame_system::Config {}
+
+	pub struct Pallet<T: Config> {
+		_config: core::marker::PhantomData<T>,
+	}
+
+	impl<T: Config> Pallet<T> {
+		pub fn new() -> Self {
+			Self { _config: core::marker::PhantomData }
+		}
+	}
+
+	impl<T: Config, R, B> crate::support::Hooks<R, B> for Pallet<T> {}
+
+	#[macros::error]
+	pub enum Error<T> {}
+
+	#[macros::call]
+	impl<T: Config> Pallet<T> {
+		pub fn transfer(&mut self, caller: T::AccountId, to: T::AccountId) -> DispatchResult {
+			Ok(())
+		}
+	}
+}
+
+#[macros::runtime(system = frame_system, block = my_types::Block)]
+pub struct Runtime {
+	frame_system: frame_system::Pallet<Self>,
+	balances: balances::Pallet<Self>,
+	vote: vote::Pallet<Self>,
+}
+
+impl frame_system::Config for Runtime {
+	type AccountId = String;
+	type PalletInfo = Self;
+	type RuntimeCall = RuntimeCall;
+	type BaseCallFilter = ();
+}
+
+impl balances::Config for Runtime {}
+
+impl vote::Config for Runtime {}
+
+fn main() {
+	use support::IsSubType;
+
+	let call: RuntimeCall = balances::Call::transfer { to: "bob".to_string() }.into();
+	assert_eq!(call, RuntimeCall::balances(balances::Call::transfer { to: "bob".to_string() }));
+	assert_eq!(call.pallet_name(), "balances");
+	assert_eq!(call.call_name(), "transfer");
+
+	let sub_call: Option<&balances::Call<Runtime>> = call.is_sub_type();
+	assert_eq!(sub_call, Some(&balances::Call::transfer { to: "bob".to_string() }));
+	let sub_call: Option<&vote::Call<Runtime>> = call.is_sub_type();
+	assert_eq!(sub_call, None);
+
+	let vote_call = vote::Call::<Runtime>::try_from(call.clone());
+	assert_eq!(vote_call, Err(call.clone()));
+	let balances_call = balances::Call::<Runtime>::try_from(call);
+	assert_eq!(balances_call, Ok(balances::Call::transfer { to: "bob".to_string() }));
+
+	let parsed: Result<RuntimeCall, _> = "vote.vote candidate=alice".parse();
+	assert_eq!(parsed, Ok(RuntimeCall::vote(vote::Call::vote { candidate: "alice".to_string() })));
+	let parsed: Result<RuntimeCall, _> = "staking.bond amount=5".parse();
+	assert_eq!(parsed, Err(support::ParseCallError::UnknownPallet("staking".to_string())));
+	let parsed: Result<RuntimeCall, _> = "balances transfer to=bob".parse();
+	assert_eq!(
+		parsed,
+		Err(support::ParseCallError::InvalidFormat("expected a call like `pallet.call`"))
+	);
+}
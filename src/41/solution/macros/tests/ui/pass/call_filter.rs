@@ -0,0 +1,154 @@
+#![allow(unused)]
+
+#[path = "../../../../src/support.rs"]
+mod support;
+
+// A minimal system pallet, with just what the runtime macro needs.
+mod frame_system {
+	pub trait Config: 'static {
+		type AccountId;
+		type PalletInfo: crate::support::PalletInfo;
+		type RuntimeCall;
+		type BaseCallFilter: crate::support::CallFilter<Self, Self::RuntimeCall>;
+	}
+
+	pub struct Pallet<T: Config> {
+		block_number: u32,
+		_config: core::marker::PhantomData<T>,
+	}
+
+	impl<T: Config> Pallet<T> {
+		pub fn new() -> Self {
+			Self { block_number: 0, _config: core::marker::PhantomData }
+		}
+
+		pub fn block_number(&self) -> u32 {
+			self.block_number
+		}
+
+		pub fn inc_block_number(&mut self) {
+			self.block_number += 1;
+		}
+
+		pub fn inc_nonce(&mut self, who: &T::AccountId) {}
+	}
+
+	// The system pallet must be callable, even if it has no calls.
+	#[macros::call]
+	impl<T: Config> Pallet<T> {}
+}
+
+mod my_types {
+	pub type Extrinsic = crate::support::Extrinsic<String, crate::RuntimeCall>;
+	pub type Header = crate::support::Header<u32>;
+	pub type Block = crate::support::Block<Header, Extrinsic>;
+}
+
+mod vote {
+	use crate::support::DispatchResult;
+
+	pub trait Config: crate::frame_system::Config {}
+
+	pub struct Pallet<T: Config> {
+		_config: core::marker::PhantomData<T>,
+	}
+
+	impl<T: Config> Pallet<T> {
+		pub fn new() -> Self {
+			Self { _config: core::marker::PhantomData }
+		}
+	}
+
+	impl<T: Config, R, B> crate::support::Hooks<R, B> for Pallet<T> {}
+
+	#[macros::error]
+	pub enum Error<T> {}
+
+	#[macros::call]
+	impl<T: Config> Pallet<T> {
+		pub fn vote(&mut self, caller: T::AccountId, candidate: T::AccountId) -> DispatchResult {
+			Ok(())
+		}
+	}
+}
+
+mod balances {
+	use crate::support::DispatchResult;
+
+	pub trait Config: crate::frame_system::Config {}
+
+	pub struct Pallet<T: Config> {
+		_config: core::marker::PhantomData<T>,
+	}
+
+	impl<T: Config> Pallet<T> {
+		pub fn new() -> Self {
+			Self { _config: core::marker::PhantomData }
+		}
+	}
+
+	impl<T: Config, R, B> crate::support::Hooks<R, B> for Pallet<T> {}
+
+	#[macros::error]
+	pub enum Error<T> {}
+
+	#[macros::call]
+	impl<T: Config> Pallet<T> {
+		pub fn transfer(&mut self, caller: T::AccountId, to: T::AccountId) -> DispatchResult {
+			Ok(())
+		}
+	}
+}
+
+#[macros::runtime(system = frame_system, block = my_types::Block)]
+pub struct Runtime {
+	frame_system: frame_system::Pallet<Self>,
+	balances: balances::Pallet<Self>,
+	vote: vote::Pallet<Self>,
+}
+
+// A filter which disables the whole `vote` pallet, and transfers to `mallory` once the first
+// block has been executed.
+pub struct BaseCallFilter;
+
+impl support::CallFilter<Runtime, RuntimeCall> for BaseCallFilter {
+	fn allows(runtime: &Runtime, call: &RuntimeCall) -> bool {
+		match call {
+			RuntimeCall::vote(_) => false,
+			RuntimeCall::balances(balances::Call::transfer { to }) =>
+				to != "mallory" || runtime.frame_system.block_number() == 0,
+			_ => true,
+		}
+	}
+}
+
+impl frame_system::Config for Runtime {
+	type AccountId = String;
+	type PalletInfo = Self;
+	type RuntimeCall = RuntimeCall;
+	type BaseCallFilter = BaseCallFilter;
+}
+
+impl balances::Config for Runtime {}
+
+impl vote::Config for Runtime {}
+
+fn main() {
+	use support::{Dispatch, DispatchError};
+
+	let mut runtime = Runtime::new();
+	let alice = "alice".to_string();
+
+	let call = RuntimeCall::balances(balances::Call::transfer { to: "bob".to_string() });
+	assert_eq!(runtime.dispatch(alice.clone(), call), Ok(()));
+
+	let call = RuntimeCall::balances(balances::Call::transfer { to: "mallory".to_string() });
+	assert_eq!(runtime.dispatch(alice.clone(), call.clone()), Ok(()));
+
+	// The filter can depend on the state of the runtime.
+	runtime.frame_system.inc_block_number();
+	assert_eq!(runtime.dispatch(alice.clone(), call), Err(DispatchError::CallFiltered));
+
+	let call = RuntimeCall::vote(vote::Call::vote { candidate: "bob".to_string() });
+	assert_eq!(runtime.dispatch(alice, call), Err(DispatchError::CallFiltered));
+}
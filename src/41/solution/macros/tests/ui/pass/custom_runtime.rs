@@ -0,0 +1,120 @@
+#![allow(unused)]
+
+#[path = "../../../../src/support.rs"]
+mod support;
+
+// A minimal system pallet, with just what the runtime macro needs.
+mod frame_system {
+	pub trait Config: 'static {
+		type AccountId;
+		type PalletInfo: crate::support::PalletInfo;
+		type RuntimeCall;
+		type BaseCallFilter: crate::support::CallFilter<Self, Self::RuntimeCall>;
+	}
+
+	pub struct Pallet<T: Config> {
+		block_number: u32,
+		_config: core::marker::PhantomData<T>,
+	}
+
+	impl<T: Config> Pallet<T> {
+		pub fn new() -> Self {
+			Self { block_number: 0, _config: core::marker::PhantomData }
+		}
+
+		pub fn block_number(&self) -> u32 {
+			self.block_number
+		}
+
+		pub fn inc_block_number(&mut self) {
+			self.block_number += 1;
+		}
+
+		pub fn inc_nonce(&mut self, who: &T::AccountId) {}
+	}
+
+	#[macros::call]
+	impl<T: Config> Pallet<T> {
+		pub fn remark(&mut self, _caller: T::AccountId, data: String) -> crate::support::DispatchResult {
+			Ok(())
+		}
+	}
+}
+
+mod my_types {
+	pub type Extrinsic = crate::support::Extrinsic<String, crate::RuntimeCall>;
+	pub type Header = crate::support::Header<u32>;
+	pub type Block = crate::support::Block<Header, Extrinsic>;
+}
+
+mod balances {
+	use crate::support::DispatchResult;
+
+	pub trait Config: crate::frame_system::Config {}
+
+	pub struct Pallet<T: Config> {
+		_config: core::marker::PhantomData<T>,
+	}
+
+	impl<T: Config> Pallet<T> {
+		pub fn new() -> Self {
+			Self { _config: core::marker::PhantomData }
+		}
+	}
+
+	impl<T: Config, R, B> crate::support::Hooks<R, B> for Pallet<T> {}
+
+	#[macros::error]
+	pub enum Error<T> {}
+
+	#[macros::call]
+	impl<T: Config> Pallet<T> {
+		pub fn transfer(&mut self, caller: T::AccountId, to: T::AccountId) -> DispatchResult {
+			Ok(())
+		}
+	}
+}
+
+// The runtime has a different name, the system pallet is not the first field, and the field
+// names do not match the pallet modules.
+#[macros::runtime(system = frame_system, block = my_types::Block)]
+pub struct TestRuntime {
+	native: balances::Pallet<Self>,
+	frame_system: frame_system::Pallet<Self>,
+}
+
+impl frame_system::Config for TestRuntime {
+	type AccountId = String;
+	type PalletInfo = Self;
+	type RuntimeCall = RuntimeCall;
+	type BaseCallFilter = ();
+}
+
+impl balances::Config for TestRuntime {}
+
+fn main() {
+	let mut runtime = TestRuntime::new();
+	let block = my_types::Block {
+		header: support::Header { block_number: 1 },
+		extrinsics: vec![support::Extrinsic {
+			caller: "alice".to_string(),
+			call: RuntimeCall::native(balances::Call::transfer { to: "bob".to_string() }),
+		}],
+	};
+	runtime.execute_block(block).unwrap();
+	assert_eq!(runtime.frame_system.block_number(), 1);
+	assert_eq!(<TestRuntime as support::PalletInfo>::index::<balances::Pallet<TestRuntime>>(), Some(0));
+	assert_eq!(<TestRuntime as support::PalletInfo>::name::<balances::Pallet<TestRuntime>>(), Some("native"));
+	// Types which are not pallets of this runtime have no index, and neither does any pallet
+	// without a runtime.
+	assert_eq!(<TestRuntime as support::PalletInfo>::index::<String>(), None);
+	assert_eq!(<() as support::PalletInfo>::index::<balances::Pallet<TestRuntime>>(), None);
+
+	// Conversions work for every pallet, including the system pallet.
+	let call: RuntimeCall = balances::Call::transfer { to: "bob".to_string() }.into();
+	assert_eq!(call.pallet_name(), "native");
+	assert!(balances::Call::<TestRuntime>::try_from(call).is_ok());
+	let call: RuntimeCall = "frame_system.remark data=hello".parse().unwrap();
+	assert_eq!(call, RuntimeCall::frame_system(frame_system::Call::remark { data: "hello".to_string() }));
+	assert_eq!(call.pallet_index(), 1);
+}
@@ -0,0 +1,149 @@
+#![allow(unused)]
+
+#[path = "../../../../src/support.rs"]
+mod support;
+
+// A minimal system pallet, with just what the runtime macro needs.
+mod frame_system {
+	pub trait Config: 'static {
+		type AccountId;
+		type PalletInfo: crate::support::PalletInfo;
+		type RuntimeCall;
+		type BaseCallFilter: crate::support::CallFilter<Self, Self::RuntimeCall>;
+	}
+
+	pub struct Pallet<T: Config> {
+		block_number: u32,
+		_config: core::marker::PhantomData<T>,
+	}
+
+	impl<T: Config> Pallet<T> {
+		pub fn new() -> Self {
+			Self { block_number: 0, _config: core::marker::PhantomData }
+		}
+
+		pub fn block_number(&self) -> u32 {
+			self.block_number
+		}
+
+		pub fn inc_block_number(&mut self) {
+			self.block_number += 1;
+		}
+
+		pub fn inc_nonce(&mut self, who: &T::AccountId) {}
+	}
+
+	// The system pallet must be callable, even if it has no calls.
+	#[macros::call]
+	impl<T: Config> Pallet<T> {}
+}
+
+mod my_types {
+	pub type Extrinsic = crate::support::Extrinsic<String, crate::RuntimeCall>;
+	pub type Header = crate::support::Header<u32>;
+	pub type Block = crate::support::Block<Header, Extrinsic>;
+}
+
+// A pallet which counts, with calls which only need the pallet itself.
+mod counter {
+	use crate::support::DispatchResult;
+
+	pub trait Config: crate::frame_system::Config {}
+
+	pub struct Pallet<T: Config> {
+		pub count: u32,
+		_config: core::marker::PhantomData<T>,
+	}
+
+	impl<T: Config> Pallet<T> {
+		pub fn new() -> Self {
+			Self { count: 0, _config: core::marker::PhantomData }
+		}
+	}
+
+	impl<T: Config, R, B> crate::support::Hooks<R, B> for Pallet<T> {}
+
+	#[macros::error]
+	pub enum Error<T> {}
+
+	#[macros::call]
+	impl<T: Config> Pallet<T> {
+		pub fn increment(&mut self, _caller: T::AccountId) -> DispatchResult {
+			self.count += 1;
+			Ok(())
+		}
+	}
+}
+
+// A pallet which copies the count of the `counter` pallet, with a call which needs the runtime.
+mod mirror {
+	use crate::support::{DispatchResult, GetPallet};
+
+	pub trait Config: crate::counter::Config {}
+
+	pub struct Pallet<T: Config> {
+		pub count: u32,
+		_config: core::marker::PhantomData<T>,
+	}
+
+	impl<T: Config> Pallet<T> {
+		pub fn new() -> Self {
+			Self { count: 0, _config: core::marker::PhantomData }
+		}
+	}
+
+	impl<T: Config, R, B> crate::support::Hooks<R, B> for Pallet<T> {}
+
+	#[macros::error]
+	pub enum Error<T> {}
+
+	#[macros::call]
+	impl<T: Config> Pallet<T> {
+		pub fn copy(runtime: &mut T, _caller: T::AccountId) -> DispatchResult
+		where
+			T: GetPallet<Self> + GetPallet<crate::counter::Pallet<T>>,
+		{
+			let count = GetPallet::<crate::counter::Pallet<T>>::pallet(runtime).count;
+			GetPallet::<Self>::pallet_mut(runtime).count = count;
+			Ok(())
+		}
+	}
+}
+
+#[macros::runtime(system = frame_system, block = my_types::Block)]
+pub struct Runtime {
+	frame_system: frame_system::Pallet<Self>,
+	counter: counter::Pallet<Self>,
+	mirror: mirror::Pallet<Self>,
+}
+
+impl frame_system::Config for Runtime {
+	type AccountId = String;
+	type PalletInfo = Self;
+	type RuntimeCall = RuntimeCall;
+	type BaseCallFilter = ();
+}
+
+impl counter::Config for Runtime {}
+
+impl mirror::Config for Runtime {}
+
+fn main() {
+	use support::Dispatch;
+
+	let mut runtime = Runtime::new();
+	let alice = "alice".to_string();
+
+	let call = RuntimeCall::counter(counter::Call::increment {});
+	assert_eq!(runtime.dispatch(alice.clone(), call.clone()), Ok(()));
+	assert_eq!(runtime.dispatch(alice.clone(), call), Ok(()));
+
+	let call = RuntimeCall::mirror(mirror::Call::copy {});
+	assert_eq!(runtime.dispatch(alice, call), Ok(()));
+	assert_eq!(runtime.mirror.count, 2);
+
+	// A pallet where every call takes `&mut self` can still dispatch calls on its own.
+	let mut counter = counter::Pallet::<Runtime>::new();
+	assert_eq!(counter.dispatch("bob".to_string(), counter::Call::increment {}), Ok(()));
+	assert_eq!(counter.count, 1);
+}
@@ -0,0 +1,120 @@
+#![allow(unused)]
+
+#[path = "../../../../src/support.rs"]
+mod support;
+
+// A minimal system pallet, with just what the runtime macro needs.
+mod frame_system {
+	pub trait Config: 'static {
+		type AccountId;
+		type PalletInfo: crate::support::PalletInfo;
+		type RuntimeCall;
+		type BaseCallFilter: crate::support::CallFilter<Self, Self::RuntimeCall>;
+	}
+
+	pub struct Pallet<T: Config> {
+		block_number: u32,
+		_config: core::marker::PhantomData<T>,
+	}
+
+	impl<T: Config> Pallet<T> {
+		pub fn new() -> Self {
+			Self { block_number: 0, _config: core::marker::PhantomData }
+		}
+
+		pub fn block_number(&self) -> u32 {
+			self.block_number
+		}
+
+		pub fn inc_block_number(&mut self) {
+			self.block_number += 1;
+		}
+
+		pub fn inc_nonce(&mut self, who: &T::AccountId) {}
+	}
+
+	// The system pallet must be callable, even if it has no calls.
+	#[macros::call]
+	impl<T: Config> Pallet<T> {}
+}
+
+mod my_types {
+	pub type Extrinsic = crate::support::Extrinsic<String, crate::RuntimeCall>;
+	pub type Header = crate::support::Header<u32>;
+	pub type Block = crate::support::Block<Header, Extrinsic>;
+}
+
+mod counter {
+	use crate::support::DispatchResult;
+
+	pub trait Config: crate::frame_system::Config {}
+
+	pub struct Pallet<T: Config> {
+		pub count: u32,
+		_config: core::marker::PhantomData<T>,
+	}
+
+	impl<T: Config> Pallet<T> {
+		pub fn new() -> Self {
+			Self { count: 0, _config: core::marker::PhantomData }
+		}
+	}
+
+	// The counter must never go above 2.
+	impl<T: Config, R, B> crate::support::Hooks<R, B> for Pallet<T> {
+		fn try_state(&self, _block_number: B) -> Result<(), &'static str> {
+			if self.count > 2 {
+				return Err("counter: count is too high")
+			}
+			Ok(())
+		}
+	}
+
+	#[macros::error]
+	pub enum Error<T> {}
+
+	#[macros::call]
+	impl<T: Config> Pallet<T> {
+		pub fn increment(&mut self, _caller: T::AccountId) -> DispatchResult {
+			self.count += 1;
+			Ok(())
+		}
+	}
+}
+
+// The runtime checks the invariants of every pallet after each block.
+#[macros::runtime(system = frame_system, block = my_types::Block, try_state)]
+pub struct Runtime {
+	frame_system: frame_system::Pallet<Self>,
+	counter: counter::Pallet<Self>,
+}
+
+impl frame_system::Config for Runtime {
+	type AccountId = String;
+	type PalletInfo = Self;
+	type RuntimeCall = RuntimeCall;
+	type BaseCallFilter = ();
+}
+
+impl counter::Config for Runtime {}
+
+fn main() {
+	let mut runtime = Runtime::new();
+	let increment = || support::Extrinsic {
+		caller: "alice".to_string(),
+		call: RuntimeCall::counter(counter::Call::increment {}),
+	};
+
+	let block = my_types::Block {
+		header: support::Header { block_number: 1 },
+		extrinsics: vec![increment(), increment()],
+	};
+	assert_eq!(runtime.execute_block(block), Ok(()));
+	assert_eq!(runtime.try_state(), Ok(()));
+
+	let block = my_types::Block {
+		header: support::Header { block_number: 2 },
+		extrinsics: vec![increment()],
+	};
+	assert_eq!(runtime.execute_block(block), Err("counter: count is too high".into()));
+}
@@ -0,0 +1,14 @@
+#![allow(unused)]
+
+mod system {
+	pub struct Pallet<T> {
+		_config: core::marker::PhantomData<T>,
+	}
+}
+
+#[macros::runtime(system = system, system = frame_system)]
+pub struct Runtime {
+	system: system::Pallet<Self>,
+}
+
+fn main() {}
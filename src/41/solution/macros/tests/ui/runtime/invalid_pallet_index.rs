@@ -0,0 +1,21 @@
+#![allow(unused)]
+
+mod system {
+	pub struct Pallet<T> {
+		_config: core::marker::PhantomData<T>,
+	}
+}
+
+mod balances {
+	pub struct Pallet<T> {
+		_config: core::marker::PhantomData<T>,
+	}
+}
+
+#[macros::runtime]
+pub struct Runtime {
+	#[pallet_index(system)]
+	system: system::Pallet<Self>,
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+#![allow(unused)]
+
+mod system {
+	pub struct Pallet<T> {
+		_config: core::marker::PhantomData<T>,
+	}
+}
+
+mod balances {
+	pub struct Pallet<T> {
+		_config: core::marker::PhantomData<T>,
+	}
+}
+
+#[macros::runtime]
+pub struct Runtime {
+	#[pallet_index(0)]
+	system: system::Pallet<Self>,
+	balances: balances::Pallet<Self>,
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+#![allow(unused)]
+
+mod system {
+	pub struct Pallet<T> {
+		_config: core::marker::PhantomData<T>,
+	}
+}
+
+mod balances {
+	pub struct Pallet<T> {
+		_config: core::marker::PhantomData<T>,
+	}
+}
+
+#[macros::runtime]
+pub struct Runtime {
+	balances: balances::Pallet<Self>,
+}
+
+fn main() {}
@@ -0,0 +1,96 @@
+#![allow(unused)]
+
+#[path = "../../../../src/support.rs"]
+mod support;
+
+// A minimal system pallet, with just what the runtime macro needs.
+mod system {
+	pub trait Config: 'static {
+		type AccountId;
+		type PalletInfo: crate::support::PalletInfo;
+		type RuntimeCall;
+		type BaseCallFilter: crate::support::CallFilter<Self, Self::RuntimeCall>;
+	}
+
+	pub struct Pallet<T: Config> {
+		block_number: u32,
+		_config: core::marker::PhantomData<T>,
+	}
+
+	impl<T: Config> Pallet<T> {
+		pub fn new() -> Self {
+			Self { block_number: 0, _config: core::marker::PhantomData }
+		}
+
+		pub fn block_number(&self) -> u32 {
+			self.block_number
+		}
+
+		pub fn inc_block_number(&mut self) {
+			self.block_number += 1;
+		}
+
+		pub fn inc_nonce(&mut self, who: &T::AccountId) {}
+	}
+
+	// The system pallet must be callable, even if it has no calls.
+	#[macros::call]
+	impl<T: Config> Pallet<T> {}
+}
+
+mod types {
+	pub type Extrinsic = crate::support::Extrinsic<String, crate::RuntimeCall>;
+	pub type Header = crate::support::Header<u32>;
+	pub type Block = crate::support::Block<Header, Extrinsic>;
+}
+
+// This module has callable functions, but the runtime uses `Storage` instead of `Pallet`, which
+// does not implement `Dispatch`.
+mod balances {
+	use crate::support::DispatchResult;
+
+	pub trait Config: crate::system::Config {}
+
+	pub struct Pallet<T: Config> {
+		_config: core::marker::PhantomData<T>,
+	}
+
+	pub struct Storage<T: Config> {
+		_config: core::marker::PhantomData<T>,
+	}
+
+	impl<T: Config> Storage<T> {
+		pub fn new() -> Self {
+			Self { _config: core::marker::PhantomData }
+		}
+	}
+
+	impl<T: Config, R, B> crate::support::Hooks<R, B> for Storage<T> {}
+
+	#[macros::error]
+	pub enum Error<T> {}
+
+	#[macros::call]
+	impl<T: Config> Pallet<T> {
+		pub fn transfer(&mut self, caller: T::AccountId, to: T::AccountId) -> DispatchResult {
+			Ok(())
+		}
+	}
+}
+
+#[macros::runtime]
+pub struct Runtime {
+	system: system::Pallet<Self>,
+	balances: balances::Storage<Self>,
+}
+
+impl system::Config for Runtime {
+	type AccountId = String;
+	type PalletInfo = Self;
+	type RuntimeCall = RuntimeCall;
+	type BaseCallFilter = ();
+}
+
+impl balances::Config for Runtime {}
+
+fn main() {}
@@ -0,0 +1,23 @@
+#![allow(unused)]
+
+mod system {
+	pub struct Pallet<T> {
+		_config: core::marker::PhantomData<T>,
+	}
+}
+
+mod balances {
+	pub struct Pallet<T> {
+		_config: core::marker::PhantomData<T>,
+	}
+}
+
+use balances::Pallet;
+
+#[macros::runtime]
+pub struct Runtime {
+	system: system::Pallet<Self>,
+	balances: Pallet<Self>,
+}
+
+fn main() {}
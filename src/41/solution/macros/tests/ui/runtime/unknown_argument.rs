@@ -0,0 +1,20 @@
+#![allow(unused)]
+
+mod system {
+	pub struct Pallet<T> {
+		_config: core::marker::PhantomData<T>,
+	}
+}
+
+mod balances {
+	pub struct Pallet<T> {
+		_config: core::marker::PhantomData<T>,
+	}
+}
+
+#[macros::runtime(system = system, pallets = all)]
+pub struct Runtime {
+	system: system::Pallet<Self>,
+}
+
+fn main() {}
@@ -0,0 +1,815 @@
+use crate::support::{
+	Currency, DispatchError, DispatchResult, Fungible, Get, GetPallet, NegativeImbalance,
+	ReservableCurrency,
+};
+use core::fmt::Debug;
+use num::traits::{CheckedAdd, CheckedSub, Zero};
+use std::collections::BTreeMap;
+
+/// The configuration trait for the Assets Module.
+pub trait Config: crate::system::Config {
+	/// A type which identifies an asset. Usually a small unsigned integer.
+	type AssetId: Ord + Copy + Debug;
+	/// A type which can represent the balance of an account for any asset.
+	type Balance: Zero + CheckedSub + CheckedAdd + Copy + PartialOrd + Debug;
+	/// The currency used to pay the deposit for creating an asset.
+	type Currency: Currency<Self, Self::AccountId, Balance: Copy + Debug>;
+	/// The amount of `Currency` reserved from the creator of an asset.
+	type AssetDeposit: Get<DepositBalanceOf<Self>>;
+	/// The maximum length of the name and symbol of an asset.
+	type StringLimit: Get<u32>;
+}
+
+/// The balance of the `Currency` used to pay deposits.
+pub type DepositBalanceOf<T> =
+	<<T as Config>::Currency as Fungible<<T as crate::system::Config>::AccountId>>::Balance;
+
+/// Information about an asset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetDetails<AccountId, Balance, DepositBalance> {
+	/// The account which created the asset. It can mint, burn and freeze the asset, and set its
+	/// metadata.
+	pub admin: AccountId,
+	/// The total amount of the asset which exists.
+	pub supply: Balance,
+	/// The minimum balance an account must have to hold the asset.
+	pub min_balance: Balance,
+	/// The amount of `Currency` reserved from the admin while the asset exists.
+	pub deposit: DepositBalance,
+}
+
+/// The balance of an account for an asset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AssetAccount<Balance> {
+	/// The balance of the account.
+	pub balance: Balance,
+	/// Whether the admin has frozen the account, so it cannot transfer the asset. A frozen account
+	/// stays frozen even when its balance is zero.
+	pub is_frozen: bool,
+}
+
+/// The name, symbol and decimals of an asset, which are only used by wallets and explorers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AssetMetadata {
+	/// The name of the asset, like `Tether USD`.
+	pub name: String,
+	/// The symbol of the asset, like `USDT`.
+	pub symbol: String,
+	/// The number of decimals used to display a balance of the asset.
+	pub decimals: u8,
+}
+
+/// The balance of an account for an asset of the Assets Module.
+pub type AssetAccountOf<T> = AssetAccount<<T as Config>::Balance>;
+
+/// Information about an asset of the Assets Module.
+pub type AssetDetailsOf<T> = AssetDetails<
+	<T as crate::system::Config>::AccountId,
+	<T as Config>::Balance,
+	DepositBalanceOf<T>,
+>;
+
+/// This is the Assets Module.
+/// It allows anyone to create their own fungible tokens, which are kept separately from the
+/// native currency.
+#[derive(Debug)]
+pub struct Pallet<T: Config> {
+	/// Information about each asset.
+	assets: BTreeMap<T::AssetId, AssetDetailsOf<T>>,
+	/// The balance of each account, for each asset.
+	accounts: BTreeMap<T::AssetId, BTreeMap<T::AccountId, AssetAccountOf<T>>>,
+	/// The metadata of each asset, if it has been set.
+	metadata: BTreeMap<T::AssetId, AssetMetadata>,
+}
+
+impl<T: Config> Pallet<T> {
+	/// Create a new instance of the Assets Module.
+	pub fn new() -> Self {
+		Self { assets: BTreeMap::new(), accounts: BTreeMap::new(), metadata: BTreeMap::new() }
+	}
+
+	/// Get the information about an asset, if it exists.
+	pub fn asset(&self, id: T::AssetId) -> Option<&AssetDetailsOf<T>> {
+		self.assets.get(&id)
+	}
+
+	/// Get the metadata of an asset, if it has been set.
+	pub fn metadata(&self, id: T::AssetId) -> Option<&AssetMetadata> {
+		self.metadata.get(&id)
+	}
+
+	/// Get the balance of an asset of an account `who`.
+	/// If the account has no stored balance, we return zero.
+	pub fn balance(&self, id: T::AssetId, who: &T::AccountId) -> T::Balance {
+		self.account(id, who)
+			.map(|account| account.balance)
+			.unwrap_or(T::Balance::zero())
+	}
+
+	/// Move `amount` of an asset from `from` to `to`.
+	///
+	/// Every account must hold at least the minimum balance of the asset, or nothing. An account
+	/// which transfers all of its balance is removed from storage.
+	pub fn do_transfer(
+		&mut self,
+		id: T::AssetId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		let details = self.assets.get(&id).ok_or(Error::<T>::Unknown)?;
+		let min_balance = details.min_balance;
+		let from_account = self.account(id, from).ok_or(Error::<T>::BalanceLow)?;
+		if from_account.is_frozen {
+			return Err(Error::<T>::Frozen.into())
+		}
+		let new_from_balance =
+			from_account.balance.checked_sub(&amount).ok_or(Error::<T>::BalanceLow)?;
+		if !new_from_balance.is_zero() && new_from_balance < min_balance {
+			return Err(Error::<T>::BelowMinimum.into())
+		}
+
+		// Transferring to yourself changes nothing, as long as you have the funds.
+		if from == to {
+			return Ok(())
+		}
+
+		let new_to_balance =
+			self.balance(id, to).checked_add(&amount).ok_or(Error::<T>::Overflow)?;
+		if new_to_balance < min_balance {
+			return Err(Error::<T>::BelowMinimum.into())
+		}
+
+		self.set_balance(id, from, new_from_balance);
+		self.set_balance(id, to, new_to_balance);
+		Ok(())
+	}
+
+	// Get the account of `who` for an asset, if it holds any.
+	fn account(&self, id: T::AssetId, who: &T::AccountId) -> Option<&AssetAccountOf<T>> {
+		self.accounts.get(&id).and_then(|accounts| accounts.get(who))
+	}
+
+	// Set the balance of `who` for an asset, keeping whether it is frozen. An account with a zero
+	// balance is removed from storage, unless it is frozen.
+	fn set_balance(&mut self, id: T::AssetId, who: &T::AccountId, balance: T::Balance) {
+		let accounts = self.accounts.entry(id).or_default();
+		let is_frozen = accounts.get(who).is_some_and(|account| account.is_frozen);
+		if balance.is_zero() && !is_frozen {
+			accounts.remove(who);
+		} else {
+			accounts
+				.entry(who.clone())
+				.or_insert(AssetAccount { balance, is_frozen: false })
+				.balance = balance;
+		}
+		if accounts.is_empty() {
+			self.accounts.remove(&id);
+		}
+	}
+
+	// Check that `who` is the admin of an asset, and return its details.
+	fn ensure_admin(
+		&mut self,
+		id: T::AssetId,
+		who: &T::AccountId,
+	) -> Result<&mut AssetDetailsOf<T>, DispatchError> {
+		let details = self.assets.get_mut(&id).ok_or(Error::<T>::Unknown)?;
+		if details.admin != *who {
+			return Err(Error::<T>::NoPermission.into())
+		}
+		Ok(details)
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Use the asset `id` as a single fungible token, through the same `Fungible` trait as the
+	/// native currency.
+	pub fn fungible(&mut self, id: T::AssetId) -> FungibleAsset<'_, T> {
+		FungibleAsset { assets: self, id }
+	}
+}
+
+/// A single asset of the Assets Module, which implements `Fungible` like the native currency.
+///
+/// Like the native currency, funds moved through the trait never remove an account: it is always
+/// left with at least the minimum balance of the asset, or nothing is moved. A frozen account
+/// cannot send or withdraw the asset.
+pub struct FungibleAsset<'a, T: Config> {
+	/// The Assets Module which stores the asset.
+	assets: &'a mut Pallet<T>,
+	/// The id of the asset.
+	id: T::AssetId,
+}
+
+impl<T: Config> FungibleAsset<'_, T> {
+	// Check that `who` can be left with `new_balance` of the asset, after sending or withdrawing
+	// some of it.
+	fn ensure_can_withdraw(&self, who: &T::AccountId, new_balance: T::Balance) -> DispatchResult {
+		let details = self.assets.asset(self.id).ok_or(Error::<T>::Unknown)?;
+		if self.assets.account(self.id, who).is_some_and(|account| account.is_frozen) {
+			return Err(Error::<T>::Frozen.into())
+		}
+		if new_balance < details.min_balance {
+			return Err(Error::<T>::BelowMinimum.into())
+		}
+		Ok(())
+	}
+}
+
+impl<T: Config> Fungible<T::AccountId> for FungibleAsset<'_, T> {
+	type Balance = T::Balance;
+
+	fn total_issuance(&self) -> T::Balance {
+		self.assets
+			.asset(self.id)
+			.map(|details| details.supply)
+			.unwrap_or(T::Balance::zero())
+	}
+
+	fn minimum_balance(&self) -> T::Balance {
+		self.assets
+			.asset(self.id)
+			.map(|details| details.min_balance)
+			.unwrap_or(T::Balance::zero())
+	}
+
+	fn free_balance(&self, who: &T::AccountId) -> T::Balance {
+		self.assets.balance(self.id, who)
+	}
+
+	fn transfer(
+		&mut self,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		let new_balance =
+			self.free_balance(from).checked_sub(&amount).ok_or(Error::<T>::BalanceLow)?;
+		self.ensure_can_withdraw(from, new_balance)?;
+		self.assets.do_transfer(self.id, from, to, amount)
+	}
+
+	fn withdraw(
+		&mut self,
+		who: &T::AccountId,
+		amount: T::Balance,
+	) -> Result<NegativeImbalance<T::Balance>, DispatchError> {
+		let new_balance =
+			self.free_balance(who).checked_sub(&amount).ok_or(Error::<T>::BalanceLow)?;
+		self.ensure_can_withdraw(who, new_balance)?;
+		let new_supply = self.total_issuance().checked_sub(&amount).ok_or(Error::<T>::Overflow)?;
+
+		self.assets.set_balance(self.id, who, new_balance);
+		if let Some(details) = self.assets.assets.get_mut(&self.id) {
+			details.supply = new_supply;
+		}
+		Ok(NegativeImbalance::new(amount))
+	}
+
+	fn deposit(&mut self, who: &T::AccountId, amount: T::Balance) -> DispatchResult {
+		let details = self.assets.asset(self.id).ok_or(Error::<T>::Unknown)?;
+		let new_supply = details.supply.checked_add(&amount).ok_or(Error::<T>::Overflow)?;
+		let new_balance =
+			self.free_balance(who).checked_add(&amount).ok_or(Error::<T>::Overflow)?;
+		if new_balance < details.min_balance {
+			return Err(Error::<T>::BelowMinimum.into())
+		}
+
+		self.assets.set_balance(self.id, who, new_balance);
+		if let Some(details) = self.assets.assets.get_mut(&self.id) {
+			details.supply = new_supply;
+		}
+		Ok(())
+	}
+}
+
+// The assets module does not need to do anything at the start of a block, but it checks that the
+// balances of each asset add up to its supply.
+impl<T: Config> crate::support::Hooks<T, T::BlockNumber> for Pallet<T> {
+	fn try_state(&self, _block_number: T::BlockNumber) -> Result<(), &'static str> {
+		for (id, details) in &self.assets {
+			let total = self
+				.accounts
+				.get(id)
+				.into_iter()
+				.flat_map(|accounts| accounts.values())
+				.try_fold(T::Balance::zero(), |total, account| total.checked_add(&account.balance))
+				.ok_or("assets: the sum of all balances overflows")?;
+			if total != details.supply {
+				return Err("assets: the sum of all balances does not match the supply")
+			}
+		}
+		Ok(())
+	}
+}
+
+/// The errors which can be returned by the Assets Module.
+#[macros::error]
+pub enum Error<T> {
+	/// An asset with this id already exists.
+	InUse,
+	/// This asset does not exist.
+	Unknown,
+	/// The minimum balance of an asset must be more than zero.
+	MinBalanceZero,
+	/// Only the admin of the asset can do this.
+	NoPermission,
+	/// The account does not have enough of the asset.
+	BalanceLow,
+	/// The account would hold less than the minimum balance of the asset.
+	BelowMinimum,
+	/// The account is frozen, and cannot transfer the asset.
+	Frozen,
+	/// The balance or supply of the asset would overflow.
+	Overflow,
+	/// The name or symbol of the asset is too long.
+	BadMetadata,
+	/// Some of the asset still exists, so the asset cannot be destroyed.
+	HasSupply,
+	/// The account does not hold the asset, so there is nothing to freeze or thaw.
+	NoAccount,
+}
+
+#[macros::call]
+impl<T: Config> Pallet<T> {
+	/// Create a new asset with the id `id`, with the caller as its admin.
+	/// The `AssetDeposit` is reserved from the caller while the asset exists.
+	#[call_index(0)]
+	pub fn create(
+		runtime: &mut T,
+		caller: T::AccountId,
+		id: T::AssetId,
+		min_balance: T::Balance,
+	) -> DispatchResult
+	where
+		T: GetPallet<Self> + GetPallet<T::Currency>,
+	{
+		let assets = GetPallet::<Self>::pallet(runtime);
+		if assets.assets.contains_key(&id) {
+			return Err(Error::<T>::InUse.into())
+		}
+		if min_balance.is_zero() {
+			return Err(Error::<T>::MinBalanceZero.into())
+		}
+
+		let deposit = T::AssetDeposit::get();
+		GetPallet::<T::Currency>::pallet_mut(runtime).reserve(&caller, deposit)?;
+		let details =
+			AssetDetails { admin: caller, supply: T::Balance::zero(), min_balance, deposit };
+		GetPallet::<Self>::pallet_mut(runtime).assets.insert(id, details);
+		Ok(())
+	}
+
+	/// Create `amount` of an asset in the account `beneficiary`.
+	/// Only the admin of the asset can mint it.
+	#[call_index(1)]
+	pub fn mint(
+		&mut self,
+		caller: T::AccountId,
+		id: T::AssetId,
+		beneficiary: T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		let details = self.ensure_admin(id, &caller)?;
+		let new_supply = details.supply.checked_add(&amount).ok_or(Error::<T>::Overflow)?;
+		let min_balance = details.min_balance;
+		let new_balance = self
+			.balance(id, &beneficiary)
+			.checked_add(&amount)
+			.ok_or(Error::<T>::Overflow)?;
+		if new_balance < min_balance {
+			return Err(Error::<T>::BelowMinimum.into())
+		}
+
+		self.set_balance(id, &beneficiary, new_balance);
+		self.ensure_admin(id, &caller)?.supply = new_supply;
+		Ok(())
+	}
+
+	/// Destroy `amount` of an asset from the account `who`.
+	/// Only the admin of the asset can burn it. If this leaves `who` with less than the minimum
+	/// balance, the rest is burned too.
+	#[call_index(2)]
+	pub fn burn(
+		&mut self,
+		caller: T::AccountId,
+		id: T::AssetId,
+		who: T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		let details = self.ensure_admin(id, &caller)?;
+		let min_balance = details.min_balance;
+		let balance = self.balance(id, &who);
+		let mut new_balance = balance.checked_sub(&amount).ok_or(Error::<T>::BalanceLow)?;
+		if new_balance < min_balance {
+			new_balance = T::Balance::zero();
+		}
+		let burned = balance.checked_sub(&new_balance).ok_or(Error::<T>::BalanceLow)?;
+
+		let details = self.ensure_admin(id, &caller)?;
+		details.supply = details.supply.checked_sub(&burned).ok_or(Error::<T>::Overflow)?;
+		self.set_balance(id, &who, new_balance);
+		Ok(())
+	}
+
+	/// Transfer `amount` of an asset from the caller to `target`.
+	#[call_index(3)]
+	pub fn transfer(
+		&mut self,
+		caller: T::AccountId,
+		id: T::AssetId,
+		target: T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		self.do_transfer(id, &caller, &target, amount)
+	}
+
+	/// Stop the account `who` from transferring an asset.
+	/// Only the admin of the asset can freeze an account.
+	#[call_index(4)]
+	pub fn freeze(
+		&mut self,
+		caller: T::AccountId,
+		id: T::AssetId,
+		who: T::AccountId,
+	) -> DispatchResult {
+		self.set_frozen(caller, id, who, true)
+	}
+
+	/// Allow a frozen account `who` to transfer an asset again.
+	/// Only the admin of the asset can thaw an account.
+	#[call_index(5)]
+	pub fn thaw(
+		&mut self,
+		caller: T::AccountId,
+		id: T::AssetId,
+		who: T::AccountId,
+	) -> DispatchResult {
+		self.set_frozen(caller, id, who, false)
+	}
+
+	/// Set the name, symbol and decimals of an asset, replacing any previous metadata.
+	/// Only the admin of the asset can set its metadata.
+	#[call_index(6)]
+	pub fn set_metadata(
+		&mut self,
+		caller: T::AccountId,
+		id: T::AssetId,
+		name: String,
+		symbol: String,
+		decimals: u8,
+	) -> DispatchResult {
+		self.ensure_admin(id, &caller)?;
+		let limit = T::StringLimit::get() as usize;
+		if name.len() > limit || symbol.len() > limit {
+			return Err(Error::<T>::BadMetadata.into())
+		}
+		self.metadata.insert(id, AssetMetadata { name, symbol, decimals });
+		Ok(())
+	}
+
+	/// Destroy an asset which has no supply, and release the `AssetDeposit` of its admin.
+	/// Only the admin of the asset can destroy it, and its id can then be used again.
+	#[call_index(7)]
+	pub fn destroy(runtime: &mut T, caller: T::AccountId, id: T::AssetId) -> DispatchResult
+	where
+		T: GetPallet<Self> + GetPallet<T::Currency>,
+	{
+		let assets = GetPallet::<Self>::pallet_mut(runtime);
+		let details = assets.ensure_admin(id, &caller)?;
+		if !details.supply.is_zero() {
+			return Err(Error::<T>::HasSupply.into())
+		}
+
+		let details = assets.assets.remove(&id).ok_or(Error::<T>::Unknown)?;
+		// With no supply, the only accounts left are frozen ones with no balance.
+		assets.accounts.remove(&id);
+		assets.metadata.remove(&id);
+		// The deposit was reserved by this pallet, so all of it can be unreserved.
+		let _ = T::Currency::unreserve(runtime, &details.admin, details.deposit);
+		Ok(())
+	}
+
+	/// Freeze or thaw the account `who`.
+	/// This is a helper function, and is not callable.
+	#[call(skip)]
+	fn set_frozen(
+		&mut self,
+		caller: T::AccountId,
+		id: T::AssetId,
+		who: T::AccountId,
+		is_frozen: bool,
+	) -> DispatchResult {
+		self.ensure_admin(id, &caller)?;
+		let account = self
+			.accounts
+			.get_mut(&id)
+			.and_then(|accounts| accounts.get_mut(&who))
+			.ok_or(Error::<T>::NoAccount)?;
+		account.is_frozen = is_frozen;
+		// A thawed account with no balance is removed from storage.
+		let balance = account.balance;
+		self.set_balance(id, &who, balance);
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{AssetAccount, AssetMetadata, Error};
+	use crate::{
+		mock::{new_test_runtime, TestRuntime},
+		support::{DispatchResult, Fungible, Hooks},
+	};
+
+	type Assets = super::Pallet<TestRuntime>;
+
+	// A runtime where alice has created asset `0`, with a minimum balance of 5.
+	fn new_runtime() -> TestRuntime {
+		let mut runtime = new_test_runtime();
+		runtime.balances.set_balance(&"alice".to_string(), 100);
+		assert_eq!(Assets::create(&mut runtime, "alice".to_string(), 0, 5), Ok(()));
+		runtime
+	}
+
+	#[test]
+	fn create() {
+		let mut runtime = new_runtime();
+		let (alice, bob) = ("alice".to_string(), "bob".to_string());
+
+		let details = runtime.assets.asset(0).unwrap();
+		assert_eq!((&details.admin, details.supply, details.deposit), (&alice, 0, 10));
+		assert_eq!(runtime.balances.balance(&alice), 90);
+		assert_eq!(runtime.balances.reserved_balance(&alice), 10);
+
+		assert_eq!(
+			Assets::create(&mut runtime, alice.clone(), 0, 5),
+			Err(Error::<TestRuntime>::InUse.into())
+		);
+		assert_eq!(
+			Assets::create(&mut runtime, alice, 1, 0),
+			Err(Error::<TestRuntime>::MinBalanceZero.into())
+		);
+		// Bob cannot pay the deposit.
+		assert_eq!(
+			Assets::create(&mut runtime, bob, 1, 5),
+			Err(crate::balances::Error::<TestRuntime>::InsufficientBalance.into())
+		);
+		assert!(runtime.assets.asset(1).is_none());
+	}
+
+	#[test]
+	fn mint_and_burn() {
+		let mut runtime = new_runtime();
+		let (alice, bob) = ("alice".to_string(), "bob".to_string());
+		let assets = &mut runtime.assets;
+
+		assert_eq!(
+			assets.mint(bob.clone(), 0, bob.clone(), 10),
+			Err(Error::<TestRuntime>::NoPermission.into())
+		);
+		assert_eq!(
+			assets.mint(alice.clone(), 1, bob.clone(), 10),
+			Err(Error::<TestRuntime>::Unknown.into())
+		);
+		assert_eq!(
+			assets.mint(alice.clone(), 0, bob.clone(), 4),
+			Err(Error::<TestRuntime>::BelowMinimum.into())
+		);
+		assert_eq!(assets.mint(alice.clone(), 0, bob.clone(), 10), Ok(()));
+		assert_eq!(assets.balance(0, &bob), 10);
+		assert_eq!(assets.asset(0).unwrap().supply, 10);
+
+		assert_eq!(
+			assets.burn(alice.clone(), 0, bob.clone(), 11),
+			Err(Error::<TestRuntime>::BalanceLow.into())
+		);
+		assert_eq!(assets.burn(alice.clone(), 0, bob.clone(), 3), Ok(()));
+		assert_eq!(assets.balance(0, &bob), 7);
+		// Burning below the minimum balance burns the rest too.
+		assert_eq!(assets.burn(alice, 0, bob.clone(), 3), Ok(()));
+		assert_eq!(assets.balance(0, &bob), 0);
+		assert_eq!(assets.account(0, &bob), None);
+		assert_eq!(assets.asset(0).unwrap().supply, 0);
+	}
+
+	#[test]
+	fn burning_a_frozen_account_keeps_it_frozen() {
+		let mut runtime = new_runtime();
+		let (alice, bob) = ("alice".to_string(), "bob".to_string());
+		let assets = &mut runtime.assets;
+		assert_eq!(assets.mint(alice.clone(), 0, bob.clone(), 10), Ok(()));
+		assert_eq!(assets.freeze(alice.clone(), 0, bob.clone()), Ok(()));
+
+		assert_eq!(assets.burn(alice.clone(), 0, bob.clone(), 10), Ok(()));
+		assert_eq!(assets.account(0, &bob), Some(&AssetAccount { balance: 0, is_frozen: true }));
+		assert_eq!(assets.try_state(0), Ok(()));
+
+		// Funds minted to the account are still frozen.
+		assert_eq!(assets.mint(alice.clone(), 0, bob.clone(), 10), Ok(()));
+		assert_eq!(
+			assets.transfer(bob.clone(), 0, alice.clone(), 5),
+			Err(Error::<TestRuntime>::Frozen.into())
+		);
+
+		// Thawing an account without any balance removes it.
+		assert_eq!(assets.burn(alice.clone(), 0, bob.clone(), 10), Ok(()));
+		assert_eq!(assets.thaw(alice, 0, bob.clone()), Ok(()));
+		assert_eq!(assets.account(0, &bob), None);
+	}
+
+	#[test]
+	fn transfer() {
+		let mut runtime = new_runtime();
+		let (alice, bob, charlie) = ("alice".to_string(), "bob".to_string(), "charlie".to_string());
+		let assets = &mut runtime.assets;
+		assert_eq!(assets.mint(alice.clone(), 0, bob.clone(), 20), Ok(()));
+
+		assert_eq!(
+			assets.transfer(bob.clone(), 0, charlie.clone(), 21),
+			Err(Error::<TestRuntime>::BalanceLow.into())
+		);
+		// Both accounts must keep at least the minimum balance, or nothing.
+		assert_eq!(
+			assets.transfer(bob.clone(), 0, charlie.clone(), 4),
+			Err(Error::<TestRuntime>::BelowMinimum.into())
+		);
+		assert_eq!(
+			assets.transfer(bob.clone(), 0, charlie.clone(), 17),
+			Err(Error::<TestRuntime>::BelowMinimum.into())
+		);
+		assert_eq!(assets.transfer(bob.clone(), 0, charlie.clone(), 15), Ok(()));
+		assert_eq!((assets.balance(0, &bob), assets.balance(0, &charlie)), (5, 15));
+
+		assert_eq!(assets.transfer(bob.clone(), 0, charlie.clone(), 5), Ok(()));
+		assert_eq!(assets.account(0, &bob), None);
+		assert_eq!(assets.balance(0, &charlie), 20);
+		assert_eq!(assets.asset(0).unwrap().supply, 20);
+		assert_eq!(assets.try_state(0), Ok(()));
+	}
+
+	#[test]
+	fn freeze_and_thaw() {
+		let mut runtime = new_runtime();
+		let (alice, bob) = ("alice".to_string(), "bob".to_string());
+		let assets = &mut runtime.assets;
+		assert_eq!(assets.mint(alice.clone(), 0, bob.clone(), 20), Ok(()));
+
+		assert_eq!(
+			assets.freeze(bob.clone(), 0, bob.clone()),
+			Err(Error::<TestRuntime>::NoPermission.into())
+		);
+		assert_eq!(assets.freeze(alice.clone(), 0, bob.clone()), Ok(()));
+		assert_eq!(assets.account(0, &bob), Some(&AssetAccount { balance: 20, is_frozen: true }));
+		assert_eq!(
+			assets.transfer(bob.clone(), 0, alice.clone(), 10),
+			Err(Error::<TestRuntime>::Frozen.into())
+		);
+		// A frozen account can still receive the asset.
+		assert_eq!(assets.mint(alice.clone(), 0, bob.clone(), 5), Ok(()));
+		assert_eq!(assets.balance(0, &bob), 25);
+
+		assert_eq!(assets.thaw(alice.clone(), 0, bob.clone()), Ok(()));
+		assert_eq!(assets.transfer(bob, 0, alice.clone(), 10), Ok(()));
+		assert_eq!(assets.balance(0, &alice), 10);
+
+		// An account which does not hold the asset cannot be frozen or thawed.
+		let charlie = "charlie".to_string();
+		assert_eq!(
+			assets.freeze(alice.clone(), 0, charlie.clone()),
+			Err(Error::<TestRuntime>::NoAccount.into())
+		);
+		assert_eq!(assets.thaw(alice, 0, charlie), Err(Error::<TestRuntime>::NoAccount.into()));
+	}
+
+	#[test]
+	fn set_metadata() {
+		let mut runtime = new_runtime();
+		let (alice, bob) = ("alice".to_string(), "bob".to_string());
+		let assets = &mut runtime.assets;
+
+		assert_eq!(
+			assets.set_metadata(bob, 0, "Gold".to_string(), "GLD".to_string(), 2),
+			Err(Error::<TestRuntime>::NoPermission.into())
+		);
+		assert_eq!(
+			assets.set_metadata(alice.clone(), 0, "Golden".to_string(), "GLD".to_string(), 2),
+			Err(Error::<TestRuntime>::BadMetadata.into())
+		);
+		assert_eq!(assets.metadata(0), None);
+
+		assert_eq!(assets.set_metadata(alice, 0, "Gold".to_string(), "GLD".to_string(), 2), Ok(()));
+		assert_eq!(
+			assets.metadata(0),
+			Some(&AssetMetadata {
+				name: "Gold".to_string(),
+				symbol: "GLD".to_string(),
+				decimals: 2
+			})
+		);
+	}
+
+	#[test]
+	fn destroy() {
+		let mut runtime = new_runtime();
+		let (alice, bob) = ("alice".to_string(), "bob".to_string());
+		assert_eq!(runtime.assets.mint(alice.clone(), 0, bob.clone(), 20), Ok(()));
+		assert_eq!(runtime.assets.freeze(alice.clone(), 0, bob.clone()), Ok(()));
+		assert_eq!(
+			runtime.assets.set_metadata(alice.clone(), 0, "Gold".into(), "GLD".into(), 2),
+			Ok(())
+		);
+
+		assert_eq!(
+			Assets::destroy(&mut runtime, bob.clone(), 0),
+			Err(Error::<TestRuntime>::NoPermission.into())
+		);
+		assert_eq!(
+			Assets::destroy(&mut runtime, alice.clone(), 0),
+			Err(Error::<TestRuntime>::HasSupply.into())
+		);
+
+		// Once the supply is burned, the asset can be destroyed, and the deposit is released.
+		assert_eq!(runtime.assets.burn(alice.clone(), 0, bob.clone(), 20), Ok(()));
+		assert_eq!(Assets::destroy(&mut runtime, alice.clone(), 0), Ok(()));
+		assert!(runtime.assets.asset(0).is_none());
+		assert_eq!(runtime.assets.account(0, &bob), None);
+		assert_eq!(runtime.assets.metadata(0), None);
+		assert_eq!(runtime.balances.balance(&alice), 100);
+		assert_eq!(runtime.balances.reserved_balance(&alice), 0);
+		assert_eq!(
+			Assets::destroy(&mut runtime, alice.clone(), 0),
+			Err(Error::<TestRuntime>::Unknown.into())
+		);
+
+		// The id can be used again.
+		assert_eq!(Assets::create(&mut runtime, alice, 0, 1), Ok(()));
+	}
+
+	// This works with any fungible token, like the native currency or an asset.
+	fn pay<F: Fungible<String>>(token: &mut F, amount: F::Balance) -> DispatchResult {
+		token.transfer(&"alice".to_string(), &"bob".to_string(), amount)
+	}
+
+	#[test]
+	fn fungible() {
+		let mut runtime = new_runtime();
+		let (alice, bob) = ("alice".to_string(), "bob".to_string());
+		assert_eq!(runtime.assets.mint(alice.clone(), 0, alice.clone(), 50), Ok(()));
+
+		assert_eq!(pay(&mut runtime.assets.fungible(0), 20), Ok(()));
+		assert_eq!(pay(&mut runtime.balances, 20), Ok(()));
+		assert_eq!(runtime.balances.free_balance(&bob), 20);
+
+		let mut gold = runtime.assets.fungible(0);
+		assert_eq!((gold.free_balance(&bob), gold.minimum_balance()), (20, 5));
+		// Like the native currency, an account is never emptied through the trait.
+		assert_eq!(gold.transfer(&bob, &alice, 20), Err(Error::<TestRuntime>::BelowMinimum.into()));
+		assert_eq!(gold.deposit(&bob, 10), Ok(()));
+		assert_eq!(gold.total_issuance(), 60);
+		assert_eq!(
+			gold.deposit(&"charlie".to_string(), 4),
+			Err(Error::<TestRuntime>::BelowMinimum.into())
+		);
+
+		let imbalance = gold.withdraw(&alice, 25).unwrap();
+		assert_eq!(gold.total_issuance(), 35);
+		assert_eq!(gold.resolve(&bob, imbalance), Ok(()));
+		assert_eq!((gold.free_balance(&alice), gold.free_balance(&bob)), (5, 55));
+		assert_eq!(gold.total_issuance(), 60);
+		assert_eq!(
+			gold.withdraw(&alice, 1).map(|i| i.peek()),
+			Err(Error::<TestRuntime>::BelowMinimum.into())
+		);
+
+		// Frozen accounts cannot withdraw the asset, and unknown assets have no supply.
+		assert_eq!(runtime.assets.freeze(alice.clone(), 0, bob.clone()), Ok(()));
+		let mut gold = runtime.assets.fungible(0);
+		assert_eq!(
+			gold.withdraw(&bob, 10).map(|i| i.peek()),
+			Err(Error::<TestRuntime>::Frozen.into())
+		);
+		assert_eq!(gold.transfer(&bob, &alice, 10), Err(Error::<TestRuntime>::Frozen.into()));
+		assert_eq!(runtime.assets.fungible(1).total_issuance(), 0);
+		assert_eq!(runtime.assets.try_state(0), Ok(()));
+	}
+
+	#[test]
+	fn parse() {
+		let call =
+			"set_metadata id=0 name=Gold symbol=GLD decimals=2".parse::<super::Call<TestRuntime>>();
+		assert_eq!(
+			call,
+			Ok(super::Call::set_metadata {
+				id: 0,
+				name: "Gold".to_string(),
+				symbol: "GLD".to_string(),
+				decimals: 2
+			})
+		);
+		assert!("set_frozen id=0 who=bob is_frozen=true"
+			.parse::<super::Call<TestRuntime>>()
+			.is_err());
+	}
+}
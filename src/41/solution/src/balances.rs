@@ -1,82 +1,719 @@
+use crate::support::{
+	BalanceStatus, DispatchError, DispatchResult, Fungible, Get, GetPallet, LockIdentifier,
+	LockableCurrency, NegativeImbalance, OnKilledAccount, ReservableCurrency,
+};
+use core::marker::PhantomData;
 use num::traits::{CheckedAdd, CheckedSub, Zero};
 use std::collections::BTreeMap;
 
 /// The configuration trait for the Balances Module.
 /// Contains the basic types needed for handling balances.
-pub trait Config: crate::system::Config {
+///
+/// This pallet can be included in the runtime more than once, for example to keep track of both a
+/// native token and some loyalty points. Each instance `I` has its own configuration.
+pub trait Config<I = ()>: crate::system::Config {
 	/// A type which can represent the balance of an account.
 	/// Usually this is a large unsigned integer.
-	type Balance: Zero + CheckedSub + CheckedAdd + Copy;
+	type Balance: Zero + CheckedSub + CheckedAdd + Copy + PartialOrd;
+	/// The minimum balance an account must have to exist. An account which would be left with
+	/// less is either kept alive by failing the transfer, or reaped.
+	type ExistentialDeposit: Get<Self::Balance>;
+	/// A hook which is called when an account is reaped, so other pallets can clean up.
+	/// Use `()` if nothing needs to happen.
+	type OnKilledAccount: OnKilledAccount<Self, Self::AccountId>;
+}
+
+/// Whether a transfer is allowed to reap the account it transfers from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExistenceRequirement {
+	/// The transfer fails if the account would be left with less than the existential deposit.
+	KeepAlive,
+	/// The account is reaped if it would be left with less than the existential deposit.
+	AllowDeath,
 }
 
 /// This is the Balances Module.
 /// It is a simple module which keeps track of how much balance each account has in this state
 /// machine.
+///
+/// Each instance `I` of this module has its own storage. The default instance is `()`.
 #[derive(Debug)]
-pub struct Pallet<T: Config> {
-	// A simple storage mapping from accounts to their balances.
+pub struct Pallet<T: Config<I>, I = ()> {
+	// A simple storage mapping from accounts to their free balances, which can be transferred.
 	balances: BTreeMap<T::AccountId, T::Balance>,
+	// The balances which accounts have reserved, for example as a deposit. These cannot be
+	// transferred, and keep the account alive.
+	reserved: BTreeMap<T::AccountId, T::Balance>,
+	// The named locks of each account. The free balance cannot go below the largest lock.
+	locks: BTreeMap<T::AccountId, BTreeMap<LockIdentifier, T::Balance>>,
+	// How much each owner allows each spender to transfer on their behalf, keyed by owner and then
+	// by spender.
+	allowances: BTreeMap<T::AccountId, BTreeMap<T::AccountId, T::Balance>>,
+	// The total amount of tokens which exist, which is always the sum of all balances.
+	total_issuance: T::Balance,
+	// The instance of this module, which is not stored.
+	_instance: PhantomData<I>,
 }
 
-impl<T: Config> Pallet<T> {
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	/// Create a new instance of the balances module.
 	pub fn new() -> Self {
-		Self { balances: BTreeMap::new() }
+		Self {
+			balances: BTreeMap::new(),
+			reserved: BTreeMap::new(),
+			locks: BTreeMap::new(),
+			allowances: BTreeMap::new(),
+			total_issuance: T::Balance::zero(),
+			_instance: PhantomData,
+		}
 	}
 
 	/// Set the balance of an account `who` to some `amount`.
+	/// The total issuance is adjusted by the difference with the previous balance.
+	///
+	/// Panics if the total issuance would overflow.
 	pub fn set_balance(&mut self, who: &T::AccountId, amount: T::Balance) {
-		self.balances.insert(who.clone(), amount);
+		let old_amount = self.balances.insert(who.clone(), amount).unwrap_or(T::Balance::zero());
+		self.total_issuance = self
+			.total_issuance
+			.checked_sub(&old_amount)
+			.and_then(|issuance| issuance.checked_add(&amount))
+			.expect("total issuance overflow");
+	}
+
+	/// Get how much `spender` is allowed to transfer on behalf of `owner`.
+	pub fn allowance(&self, owner: &T::AccountId, spender: &T::AccountId) -> T::Balance {
+		self.allowances
+			.get(owner)
+			.and_then(|allowances| allowances.get(spender))
+			.copied()
+			.unwrap_or(T::Balance::zero())
+	}
+
+	/// Get the total amount of tokens which exist.
+	pub fn total_issuance(&self) -> T::Balance {
+		self.total_issuance
 	}
 
-	/// Get the balance of an account `who`.
+	/// Get the free balance of an account `who`, which does not include reserved funds.
 	/// If the account has no stored balance, we return zero.
 	pub fn balance(&self, who: &T::AccountId) -> T::Balance {
 		*self.balances.get(who).unwrap_or(&T::Balance::zero())
 	}
+
+	/// Get the reserved balance of an account `who`, which cannot be transferred.
+	/// If the account has no reserved funds, we return zero.
+	pub fn reserved_balance(&self, who: &T::AccountId) -> T::Balance {
+		*self.reserved.get(who).unwrap_or(&T::Balance::zero())
+	}
+
+	/// Move `amount` from the account `from` to the account `to`.
+	///
+	/// If `from` would be left with less than the existential deposit, it is either kept alive by
+	/// failing the transfer, or reaped, depending on `existence`. An account with reserved funds is
+	/// never reaped, and an account with a lock cannot be reaped. A reaped account is removed from
+	/// storage, and any balance it has left is lost. The free balance of `from` cannot go below its
+	/// locks. Only an account which exists can be reaped, and transferring zero does nothing.
+	/// Returns whether `from` was reaped, so the caller can call the `OnKilledAccount` hook.
+	pub fn do_transfer(
+		&mut self,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: T::Balance,
+		existence: ExistenceRequirement,
+	) -> Result<bool, DispatchError> {
+		if amount.is_zero() {
+			return Ok(false)
+		}
+		let existential_deposit = T::ExistentialDeposit::get();
+		let new_from_balance = self
+			.balance(from)
+			.checked_sub(&amount)
+			.ok_or(Error::<T, I>::InsufficientBalance)?;
+		self.ensure_can_withdraw(from, new_from_balance)?;
+		let reap = self.should_reap(from, new_from_balance);
+		if reap && existence == ExistenceRequirement::KeepAlive {
+			return Err(Error::<T, I>::KeepAlive.into())
+		}
+		if reap {
+			self.ensure_can_reap(from)?;
+		}
+
+		// Transferring to yourself changes nothing, as long as you have the funds.
+		if from == to {
+			return Ok(false)
+		}
+
+		// An account which does not exist yet can only be created with the existential deposit.
+		let new_to_balance =
+			self.balance(to).checked_add(&amount).ok_or(Error::<T, I>::Overflow)?;
+		if new_to_balance < existential_deposit && self.reserved_balance(to).is_zero() {
+			return Err(Error::<T, I>::ExistentialDeposit.into())
+		}
+
+		// Any dust left in a reaped account no longer exists.
+		let dust = if reap { new_from_balance } else { T::Balance::zero() };
+		let new_issuance =
+			self.total_issuance.checked_sub(&dust).ok_or(Error::<T, I>::Underflow)?;
+
+		self.total_issuance = new_issuance;
+		if reap {
+			self.balances.remove(from);
+			self.allowances.remove(from);
+		} else {
+			self.balances.insert(from.clone(), new_from_balance);
+		}
+		self.balances.insert(to.clone(), new_to_balance);
+
+		Ok(reap)
+	}
+
+	/// Destroy `amount` from the account `who`, reducing the total issuance.
+	///
+	/// If `who` would be left with less than the existential deposit, and has no reserved funds, it
+	/// is reaped and its dust is destroyed too. An account with a lock cannot be reaped, and its
+	/// free balance cannot go below its locks. Only an account which exists can be reaped, and
+	/// burning zero does nothing. Returns whether `who` was reaped, so the caller can call the
+	/// `OnKilledAccount` hook.
+	pub fn do_burn(
+		&mut self,
+		who: &T::AccountId,
+		amount: T::Balance,
+	) -> Result<bool, DispatchError> {
+		if amount.is_zero() {
+			return Ok(false)
+		}
+		let new_balance = self
+			.balance(who)
+			.checked_sub(&amount)
+			.ok_or(Error::<T, I>::InsufficientBalance)?;
+		self.ensure_can_withdraw(who, new_balance)?;
+		let reap = self.should_reap(who, new_balance);
+		if reap {
+			self.ensure_can_reap(who)?;
+		}
+		let burned = if reap { self.balance(who) } else { amount };
+		self.total_issuance =
+			self.total_issuance.checked_sub(&burned).ok_or(Error::<T, I>::Underflow)?;
+
+		if reap {
+			self.balances.remove(who);
+			self.allowances.remove(who);
+		} else {
+			self.balances.insert(who.clone(), new_balance);
+		}
+
+		Ok(reap)
+	}
+
+	// Set the allowance of `spender` on behalf of `owner`, removing it from storage when it is
+	// zero.
+	fn set_allowance(&mut self, owner: &T::AccountId, spender: &T::AccountId, amount: T::Balance) {
+		if amount.is_zero() {
+			if let Some(allowances) = self.allowances.get_mut(owner) {
+				allowances.remove(spender);
+				if allowances.is_empty() {
+					self.allowances.remove(owner);
+				}
+			}
+		} else {
+			self.allowances
+				.entry(owner.clone())
+				.or_default()
+				.insert(spender.clone(), amount);
+		}
+	}
+
+	// Check that the free balance of `who` can become `new_balance` without breaking its locks.
+	fn ensure_can_withdraw(&self, who: &T::AccountId, new_balance: T::Balance) -> DispatchResult {
+		if new_balance < self.frozen_balance(who) {
+			return Err(Error::<T, I>::LiquidityRestrictions.into())
+		}
+		Ok(())
+	}
+
+	// Whether `who` would be reaped if its free balance became `new_balance`. Only an account
+	// which exists, and has no reserved funds, can be reaped.
+	fn should_reap(&self, who: &T::AccountId, new_balance: T::Balance) -> bool {
+		self.balances.contains_key(who) &&
+			new_balance < T::ExistentialDeposit::get() &&
+			self.reserved_balance(who).is_zero()
+	}
+
+	// Check that `who` has no locks, since reaping the account would remove them.
+	fn ensure_can_reap(&self, who: &T::AccountId) -> DispatchResult {
+		if self.locks.contains_key(who) {
+			return Err(Error::<T, I>::LiquidityRestrictions.into())
+		}
+		Ok(())
+	}
+
+	// Split `amount` into the part which `who` has reserved, and the rest.
+	fn reserved_part(&self, who: &T::AccountId, amount: T::Balance) -> (T::Balance, T::Balance) {
+		let reserved = self.reserved_balance(who);
+		match amount.checked_sub(&reserved) {
+			Some(remaining) => (reserved, remaining),
+			None => (amount, T::Balance::zero()),
+		}
+	}
+
+	/// Move up to `amount` from the reserved balance of `who` back to its free balance.
+	///
+	/// If this leaves `who` without reserved funds and with less than the existential deposit, it
+	/// is reaped, unless it has a lock. Returns the amount which could not be unreserved, and
+	/// whether `who` was reaped, so the caller can call the `OnKilledAccount` hook.
+	pub fn do_unreserve(&mut self, who: &T::AccountId, amount: T::Balance) -> (T::Balance, bool) {
+		let (actual, remaining) = self.reserved_part(who, amount);
+		let Some(new_free) = self.balance(who).checked_add(&actual) else { return (amount, false) };
+		self.take_reserved(who, actual);
+		self.balances.insert(who.clone(), new_free);
+		(remaining, self.reap_dust(who))
+	}
+
+	/// Destroy up to `amount` of the reserved balance of `who`, reducing the total issuance.
+	///
+	/// Like `do_unreserve`, this reaps `who` if it is left without reserved funds and with less
+	/// than the existential deposit. Returns the amount which could not be slashed, and whether
+	/// `who` was reaped.
+	pub fn do_slash_reserved(
+		&mut self,
+		who: &T::AccountId,
+		amount: T::Balance,
+	) -> (T::Balance, bool) {
+		let (actual, remaining) = self.reserved_part(who, amount);
+		// The total issuance always includes every reserved balance, so this should never fail.
+		let Some(new_issuance) = self.total_issuance.checked_sub(&actual) else {
+			return (amount, false)
+		};
+		self.take_reserved(who, actual);
+		self.total_issuance = new_issuance;
+		(remaining, self.reap_dust(who))
+	}
+
+	/// Move up to `amount` from the reserved balance of `slashed` to the free or reserved balance
+	/// of `beneficiary`, which must already exist.
+	///
+	/// Like `do_unreserve`, this reaps `slashed` if it is left without reserved funds and with less
+	/// than the existential deposit. Returns the amount which could not be moved, and whether
+	/// `slashed` was reaped.
+	pub fn do_repatriate_reserved(
+		&mut self,
+		slashed: &T::AccountId,
+		beneficiary: &T::AccountId,
+		amount: T::Balance,
+		status: BalanceStatus,
+	) -> Result<(T::Balance, bool), DispatchError> {
+		if !self.balances.contains_key(beneficiary) && !self.reserved.contains_key(beneficiary) {
+			return Err(Error::<T, I>::DeadAccount.into())
+		}
+
+		// Moving funds within the same account only changes where they are kept.
+		if slashed == beneficiary {
+			return Ok(match status {
+				BalanceStatus::Free => self.do_unreserve(slashed, amount),
+				BalanceStatus::Reserved => (self.reserved_part(slashed, amount).1, false),
+			})
+		}
+
+		let (actual, remaining) = self.reserved_part(slashed, amount);
+		match status {
+			BalanceStatus::Free => {
+				let new_free = self
+					.balance(beneficiary)
+					.checked_add(&actual)
+					.ok_or(Error::<T, I>::Overflow)?;
+				self.balances.insert(beneficiary.clone(), new_free);
+			},
+			BalanceStatus::Reserved => {
+				let new_reserved = self
+					.reserved_balance(beneficiary)
+					.checked_add(&actual)
+					.ok_or(Error::<T, I>::Overflow)?;
+				self.reserved.insert(beneficiary.clone(), new_reserved);
+			},
+		}
+		self.take_reserved(slashed, actual);
+		Ok((remaining, self.reap_dust(slashed)))
+	}
+
+	// Reap `who` if it has no reserved funds left, and less than the existential deposit, since
+	// nothing else keeps the account alive. An account with a lock is kept. Any dust is destroyed.
+	// Returns whether `who` was reaped.
+	fn reap_dust(&mut self, who: &T::AccountId) -> bool {
+		let balance = self.balance(who);
+		if !self.should_reap(who, balance) || self.ensure_can_reap(who).is_err() {
+			return false
+		}
+		// The total issuance always includes the balance of every account, so this never fails.
+		let Some(new_issuance) = self.total_issuance.checked_sub(&balance) else { return false };
+		self.total_issuance = new_issuance;
+		self.balances.remove(who);
+		self.allowances.remove(who);
+		true
+	}
+
+	// Remove `amount` from the reserved balance of `who`, which must have at least that much
+	// reserved. The reserved balance is removed from storage once it is zero.
+	fn take_reserved(&mut self, who: &T::AccountId, amount: T::Balance) {
+		let reserved =
+			self.reserved_balance(who).checked_sub(&amount).unwrap_or(T::Balance::zero());
+		if reserved.is_zero() {
+			self.reserved.remove(who);
+		} else {
+			self.reserved.insert(who.clone(), reserved);
+		}
+	}
+}
+
+// The balances module does not need to do anything at the start of a block, but it checks that
+// the free and reserved balances of all accounts add up to the total issuance.
+impl<T: Config<I>, I: 'static> crate::support::Hooks<T, T::BlockNumber> for Pallet<T, I> {
+	fn try_state(&self, _block_number: T::BlockNumber) -> Result<(), &'static str> {
+		let total = self
+			.balances
+			.values()
+			.chain(self.reserved.values())
+			.try_fold(T::Balance::zero(), |total, balance| total.checked_add(balance))
+			.ok_or("balances: the sum of all balances overflows")?;
+		if total != self.total_issuance {
+			return Err("balances: the sum of all balances does not match the total issuance")
+		}
+		Ok(())
+	}
+}
+
+/// The errors which can be returned by the Balances Module.
+#[macros::error]
+pub enum Error<T, I = ()> {
+	/// Not enough funds.
+	InsufficientBalance,
+	/// The balance of an account would overflow.
+	Overflow,
+	/// The amount is too low to create a new account.
+	ExistentialDeposit,
+	/// The transfer would leave the account with less than the existential deposit.
+	KeepAlive,
+	/// The beneficiary account does not exist.
+	DeadAccount,
+	/// The funds are locked, and cannot be moved.
+	LiquidityRestrictions,
+	/// The spender is not allowed to transfer this much on behalf of the owner.
+	InsufficientAllowance,
+	/// The total issuance would go below zero.
+	Underflow,
+}
+
+impl<T: Config<I>, I: 'static> Fungible<T::AccountId> for Pallet<T, I> {
+	type Balance = T::Balance;
+
+	fn total_issuance(&self) -> T::Balance {
+		self.total_issuance
+	}
+
+	fn minimum_balance(&self) -> T::Balance {
+		T::ExistentialDeposit::get()
+	}
+
+	fn free_balance(&self, who: &T::AccountId) -> T::Balance {
+		self.balance(who)
+	}
+
+	fn transfer(
+		&mut self,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		self.do_transfer(from, to, amount, ExistenceRequirement::KeepAlive)?;
+		Ok(())
+	}
+
+	fn withdraw(
+		&mut self,
+		who: &T::AccountId,
+		amount: T::Balance,
+	) -> Result<NegativeImbalance<T::Balance>, DispatchError> {
+		let new_balance = self
+			.balance(who)
+			.checked_sub(&amount)
+			.ok_or(Error::<T, I>::InsufficientBalance)?;
+		self.ensure_can_withdraw(who, new_balance)?;
+		if new_balance < T::ExistentialDeposit::get() && self.reserved_balance(who).is_zero() {
+			return Err(Error::<T, I>::KeepAlive.into())
+		}
+		let new_issuance =
+			self.total_issuance.checked_sub(&amount).ok_or(Error::<T, I>::Underflow)?;
+
+		self.balances.insert(who.clone(), new_balance);
+		self.total_issuance = new_issuance;
+		Ok(NegativeImbalance::new(amount))
+	}
+
+	fn deposit(&mut self, who: &T::AccountId, amount: T::Balance) -> DispatchResult {
+		let new_balance = self.balance(who).checked_add(&amount).ok_or(Error::<T, I>::Overflow)?;
+		if new_balance < T::ExistentialDeposit::get() && self.reserved_balance(who).is_zero() {
+			return Err(Error::<T, I>::ExistentialDeposit.into())
+		}
+		let new_issuance =
+			self.total_issuance.checked_add(&amount).ok_or(Error::<T, I>::Overflow)?;
+
+		self.balances.insert(who.clone(), new_balance);
+		self.total_issuance = new_issuance;
+		Ok(())
+	}
+}
+
+impl<T, I: 'static> ReservableCurrency<T, T::AccountId> for Pallet<T, I>
+where
+	T: Config<I> + GetPallet<Self>,
+{
+	fn reserved_balance(&self, who: &T::AccountId) -> T::Balance {
+		Self::reserved_balance(self, who)
+	}
+
+	fn can_reserve(&self, who: &T::AccountId, amount: T::Balance) -> bool {
+		self.balance(who)
+			.checked_sub(&amount)
+			.is_some_and(|new_free| self.ensure_can_withdraw(who, new_free).is_ok()) &&
+			self.reserved_balance(who).checked_add(&amount).is_some()
+	}
+
+	fn reserve(&mut self, who: &T::AccountId, amount: T::Balance) -> DispatchResult {
+		let new_free = self
+			.balance(who)
+			.checked_sub(&amount)
+			.ok_or(Error::<T, I>::InsufficientBalance)?;
+		self.ensure_can_withdraw(who, new_free)?;
+		let new_reserved =
+			self.reserved_balance(who).checked_add(&amount).ok_or(Error::<T, I>::Overflow)?;
+		self.balances.insert(who.clone(), new_free);
+		self.reserved.insert(who.clone(), new_reserved);
+		Ok(())
+	}
+
+	fn unreserve(runtime: &mut T, who: &T::AccountId, amount: T::Balance) -> T::Balance {
+		let (remaining, reaped) = GetPallet::<Self>::pallet_mut(runtime).do_unreserve(who, amount);
+		if reaped {
+			T::OnKilledAccount::on_killed_account(runtime, who);
+		}
+		remaining
+	}
+
+	fn slash_reserved(runtime: &mut T, who: &T::AccountId, amount: T::Balance) -> T::Balance {
+		let (remaining, reaped) =
+			GetPallet::<Self>::pallet_mut(runtime).do_slash_reserved(who, amount);
+		if reaped {
+			T::OnKilledAccount::on_killed_account(runtime, who);
+		}
+		remaining
+	}
+
+	fn repatriate_reserved(
+		runtime: &mut T,
+		slashed: &T::AccountId,
+		beneficiary: &T::AccountId,
+		amount: T::Balance,
+		status: BalanceStatus,
+	) -> Result<T::Balance, DispatchError> {
+		let (remaining, reaped) = GetPallet::<Self>::pallet_mut(runtime).do_repatriate_reserved(
+			slashed,
+			beneficiary,
+			amount,
+			status,
+		)?;
+		if reaped {
+			T::OnKilledAccount::on_killed_account(runtime, slashed);
+		}
+		Ok(remaining)
+	}
+}
+
+impl<T: Config<I>, I: 'static> LockableCurrency<T::AccountId> for Pallet<T, I> {
+	fn frozen_balance(&self, who: &T::AccountId) -> T::Balance {
+		let Some(locks) = self.locks.get(who) else { return T::Balance::zero() };
+		locks.values().fold(
+			T::Balance::zero(),
+			|frozen, &amount| {
+				if amount > frozen {
+					amount
+				} else {
+					frozen
+				}
+			},
+		)
+	}
+
+	fn set_lock(&mut self, id: LockIdentifier, who: &T::AccountId, amount: T::Balance) {
+		if amount.is_zero() {
+			return self.remove_lock(id, who)
+		}
+		self.locks.entry(who.clone()).or_default().insert(id, amount);
+	}
+
+	fn extend_lock(&mut self, id: LockIdentifier, who: &T::AccountId, amount: T::Balance) {
+		let existing = self.locks.get(who).and_then(|locks| locks.get(&id)).copied();
+		if existing.is_some_and(|existing| existing >= amount) {
+			return
+		}
+		self.set_lock(id, who, amount);
+	}
+
+	fn remove_lock(&mut self, id: LockIdentifier, who: &T::AccountId) {
+		if let Some(locks) = self.locks.get_mut(who) {
+			locks.remove(&id);
+			if locks.is_empty() {
+				self.locks.remove(who);
+			}
+		}
+	}
 }
 
 #[macros::call]
-impl<T: Config> Pallet<T> {
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	/// Transfer `amount` from one account to another.
 	/// This function verifies that `from` has at least `amount` balance to transfer,
 	/// and that no mathematical overflows occur.
+	/// The transfer fails if it would leave the caller with less than the existential deposit.
+	#[call_index(0)]
 	pub fn transfer(
 		&mut self,
 		caller: T::AccountId,
 		to: T::AccountId,
 		amount: T::Balance,
-	) -> crate::support::DispatchResult {
-		let caller_balance = self.balance(&caller);
-		let to_balance = self.balance(&to);
+	) -> DispatchResult {
+		self.do_transfer(&caller, &to, amount, ExistenceRequirement::KeepAlive)?;
+		Ok(())
+	}
 
-		let new_caller_balance = caller_balance.checked_sub(&amount).ok_or("Not enough funds.")?;
-		let new_to_balance = to_balance.checked_add(&amount).ok_or("Overflow")?;
+	/// Transfer `amount` from one account to another, like `transfer`.
+	/// If this leaves the caller with less than the existential deposit, the caller's account is
+	/// reaped.
+	#[call_index(1)]
+	pub fn transfer_allow_death(
+		runtime: &mut T,
+		caller: T::AccountId,
+		to: T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult
+	where
+		T: GetPallet<Self>,
+	{
+		let balances = GetPallet::<Self>::pallet_mut(runtime);
+		if balances.do_transfer(&caller, &to, amount, ExistenceRequirement::AllowDeath)? {
+			T::OnKilledAccount::on_killed_account(runtime, &caller);
+		}
+		Ok(())
+	}
 
-		self.balances.insert(caller, new_caller_balance);
-		self.balances.insert(to, new_to_balance);
+	/// Create `amount` new tokens in the account `to`, increasing the total issuance.
+	/// Only the root account can mint tokens.
+	#[call_index(2)]
+	pub fn mint(
+		&mut self,
+		caller: T::AccountId,
+		to: T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		crate::system::ensure_root::<T>(&caller)?;
+		let new_issuance =
+			self.total_issuance.checked_add(&amount).ok_or(Error::<T, I>::Overflow)?;
+		let new_balance = self.balance(&to).checked_add(&amount).ok_or(Error::<T, I>::Overflow)?;
+		if new_balance < T::ExistentialDeposit::get() && self.reserved_balance(&to).is_zero() {
+			return Err(Error::<T, I>::ExistentialDeposit.into())
+		}
 
+		self.balances.insert(to, new_balance);
+		self.total_issuance = new_issuance;
 		Ok(())
 	}
-}
 
-#[cfg(test)]
-mod tests {
-	struct TestConfig;
+	/// Destroy `amount` tokens from the account `from`, reducing the total issuance.
+	/// Only the root account can burn tokens. If this leaves `from` with less than the existential
+	/// deposit, the account is reaped.
+	#[call_index(3)]
+	pub fn burn(
+		runtime: &mut T,
+		caller: T::AccountId,
+		from: T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult
+	where
+		T: GetPallet<Self>,
+	{
+		crate::system::ensure_root::<T>(&caller)?;
+		let balances = GetPallet::<Self>::pallet_mut(runtime);
+		if balances.do_burn(&from, amount)? {
+			T::OnKilledAccount::on_killed_account(runtime, &from);
+		}
+		Ok(())
+	}
+
+	/// Allow `spender` to transfer up to `amount` on behalf of the caller, replacing any previous
+	/// allowance. Approving zero removes the allowance.
+	#[call_index(4)]
+	pub fn approve(
+		&mut self,
+		caller: T::AccountId,
+		spender: T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		self.set_allowance(&caller, &spender, amount);
+		Ok(())
+	}
 
-	impl crate::system::Config for TestConfig {
-		type AccountId = String;
-		type BlockNumber = u32;
-		type Nonce = u32;
+	/// Transfer `amount` from `owner` to another account, on behalf of `owner`.
+	/// The caller must have been allowed to transfer at least `amount` with `approve`, and the
+	/// allowance is reduced by `amount`. Like `transfer`, this keeps `owner` alive.
+	#[call_index(5)]
+	pub fn transfer_from(
+		&mut self,
+		caller: T::AccountId,
+		owner: T::AccountId,
+		to: T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		let new_allowance = self
+			.allowance(&owner, &caller)
+			.checked_sub(&amount)
+			.ok_or(Error::<T, I>::InsufficientAllowance)?;
+		self.do_transfer(&owner, &to, amount, ExistenceRequirement::KeepAlive)?;
+		self.set_allowance(&owner, &caller, new_allowance);
+		Ok(())
 	}
 
-	impl super::Config for TestConfig {
-		type Balance = u128;
+	/// Reduce the allowance of `spender` on behalf of the caller by `amount`.
+	/// This fails if the allowance is less than `amount`, for example because the spender has
+	/// already used some of it.
+	#[call_index(6)]
+	pub fn decrease_allowance(
+		&mut self,
+		caller: T::AccountId,
+		spender: T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		let new_allowance = self
+			.allowance(&caller, &spender)
+			.checked_sub(&amount)
+			.ok_or(Error::<T, I>::InsufficientAllowance)?;
+		self.set_allowance(&caller, &spender, new_allowance);
+		Ok(())
 	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Error;
+	use crate::{
+		mock::{execute_next_block, new_test_runtime, TestRuntime},
+		support::{
+			BalanceStatus, Currency, Decode, DispatchError, Encode, Fungible, Hooks, Instance1,
+			LockableCurrency, ReservableCurrency,
+		},
+	};
 
 	#[test]
 	fn init_balances() {
-		let mut balances = super::Pallet::<TestConfig>::new();
+		let mut balances = super::Pallet::<TestRuntime>::new();
 
 		assert_eq!(balances.balance(&"alice".to_string()), 0);
 		balances.set_balance(&"alice".to_string(), 100);
@@ -86,11 +723,11 @@ mod tests {
 
 	#[test]
 	fn transfer_balance() {
-		let mut balances = super::Pallet::<TestConfig>::new();
+		let mut balances = super::Pallet::<TestRuntime>::new();
 
 		assert_eq!(
 			balances.transfer("alice".to_string(), "bob".to_string(), 51),
-			Err("Not enough funds.")
+			Err(Error::<TestRuntime>::InsufficientBalance.into())
 		);
 
 		balances.set_balance(&"alice".to_string(), 100);
@@ -100,7 +737,587 @@ mod tests {
 
 		assert_eq!(
 			balances.transfer("alice".to_string(), "bob".to_string(), 51),
-			Err("Not enough funds.")
+			Err(Error::<TestRuntime>::InsufficientBalance.into())
+		);
+	}
+
+	#[test]
+	fn existential_deposit() {
+		let mut runtime = new_test_runtime();
+		let (alice, bob) = ("alice".to_string(), "bob".to_string());
+		runtime.balances.set_balance(&alice, 20);
+		runtime.system.inc_nonce(&alice);
+
+		// A new account must receive at least the existential deposit.
+		assert_eq!(
+			runtime.balances.transfer(alice.clone(), bob.clone(), 4),
+			Err(Error::<TestRuntime>::ExistentialDeposit.into())
+		);
+		// `transfer` keeps the caller alive, even when sending everything.
+		assert_eq!(
+			runtime.balances.transfer(alice.clone(), bob.clone(), 16),
+			Err(Error::<TestRuntime>::KeepAlive.into())
+		);
+		assert_eq!(
+			runtime.balances.transfer(alice.clone(), bob.clone(), 20),
+			Err(Error::<TestRuntime>::KeepAlive.into())
+		);
+		assert_eq!(runtime.balances.balance(&alice), 20);
+
+		// `transfer_allow_death` reaps the caller, which loses its dust and its nonce.
+		assert_eq!(
+			super::Pallet::<TestRuntime>::transfer_allow_death(
+				&mut runtime,
+				alice.clone(),
+				bob.clone(),
+				16
+			),
+			Ok(())
+		);
+		assert_eq!(runtime.balances.balance(&alice), 0);
+		assert_eq!(runtime.balances.balance(&bob), 16);
+		assert!(!runtime.balances.balances.contains_key(&alice));
+		assert_eq!(runtime.system.nonce(&alice), 0);
+		// The dust no longer exists.
+		assert_eq!(runtime.balances.total_issuance(), 16);
+		assert_eq!(runtime.balances.try_state(0), Ok(()));
+
+		// Transfers which leave enough behind do not reap anyone.
+		runtime.system.inc_nonce(&bob);
+		assert_eq!(
+			super::Pallet::<TestRuntime>::transfer_allow_death(
+				&mut runtime,
+				bob.clone(),
+				alice.clone(),
+				10
+			),
+			Ok(())
 		);
+		assert_eq!(runtime.balances.balance(&bob), 6);
+		assert_eq!(runtime.system.nonce(&bob), 1);
+	}
+
+	#[test]
+	fn total_issuance() {
+		let mut balances = super::Pallet::<TestRuntime>::new();
+		let (alice, bob) = ("alice".to_string(), "bob".to_string());
+
+		balances.set_balance(&alice, 100);
+		balances.set_balance(&bob, 50);
+		assert_eq!(balances.total_issuance(), 150);
+		balances.set_balance(&alice, 20);
+		assert_eq!(balances.total_issuance(), 70);
+		assert_eq!(balances.try_state(0), Ok(()));
+
+		// Transfers move tokens around, without creating or destroying any.
+		assert_eq!(balances.transfer(alice.clone(), bob.clone(), 10), Ok(()));
+		assert_eq!(balances.total_issuance(), 70);
+		assert_eq!(balances.try_state(0), Ok(()));
+
+		// Changing a balance without the total issuance breaks the invariant.
+		balances.balances.insert(bob, 1);
+		assert!(balances.try_state(0).is_err());
+	}
+
+	#[test]
+	fn mint_and_burn() {
+		let mut runtime = new_test_runtime();
+		let (root, alice, bob) = ("root".to_string(), "alice".to_string(), "bob".to_string());
+		runtime.balances.set_balance(&alice, 100);
+
+		// Only root can mint and burn.
+		assert_eq!(
+			runtime.balances.mint(alice.clone(), bob.clone(), 10),
+			Err(DispatchError::BadOrigin)
+		);
+		assert_eq!(
+			super::Pallet::<TestRuntime>::burn(&mut runtime, alice.clone(), alice.clone(), 10),
+			Err(DispatchError::BadOrigin)
+		);
+
+		// A new account must receive at least the existential deposit.
+		assert_eq!(
+			runtime.balances.mint(root.clone(), bob.clone(), 4),
+			Err(Error::<TestRuntime>::ExistentialDeposit.into())
+		);
+		assert_eq!(runtime.balances.mint(root.clone(), bob.clone(), 10), Ok(()));
+		assert_eq!(runtime.balances.balance(&bob), 10);
+		assert_eq!(runtime.balances.total_issuance(), 110);
+		assert_eq!(
+			runtime.balances.mint(root.clone(), bob.clone(), u128::MAX),
+			Err(Error::<TestRuntime>::Overflow.into())
+		);
+
+		assert_eq!(
+			super::Pallet::<TestRuntime>::burn(&mut runtime, root.clone(), alice.clone(), 101),
+			Err(Error::<TestRuntime>::InsufficientBalance.into())
+		);
+		assert_eq!(
+			super::Pallet::<TestRuntime>::burn(&mut runtime, root.clone(), alice.clone(), 30),
+			Ok(())
+		);
+		assert_eq!(runtime.balances.balance(&alice), 70);
+		assert_eq!(runtime.balances.total_issuance(), 80);
+
+		// Burning below the existential deposit reaps the account and destroys its dust.
+		runtime.system.inc_nonce(&bob);
+		assert_eq!(
+			super::Pallet::<TestRuntime>::burn(&mut runtime, root.clone(), bob.clone(), 6),
+			Ok(())
+		);
+		assert_eq!(runtime.balances.balance(&bob), 0);
+		assert_eq!(runtime.system.nonce(&bob), 0);
+		assert_eq!(runtime.balances.total_issuance(), 70);
+		assert_eq!(runtime.balances.try_state(0), Ok(()));
+	}
+
+	#[test]
+	fn reserve_and_unreserve() {
+		let mut runtime = new_test_runtime();
+		let (alice, bob) = ("alice".to_string(), "bob".to_string());
+		runtime.balances.set_balance(&alice, 100);
+
+		assert!(!runtime.balances.can_reserve(&alice, 101));
+		assert_eq!(
+			runtime.balances.reserve(&alice, 101),
+			Err(Error::<TestRuntime>::InsufficientBalance.into())
+		);
+		assert!(runtime.balances.can_reserve(&alice, 100));
+		assert_eq!(runtime.balances.reserve(&alice, 60), Ok(()));
+		assert_eq!(runtime.balances.balance(&alice), 40);
+		assert_eq!(runtime.balances.reserved_balance(&alice), 60);
+		assert_eq!(runtime.balances.total_issuance(), 100);
+		assert_eq!(runtime.balances.try_state(0), Ok(()));
+
+		// Reserved funds cannot be transferred.
+		assert_eq!(
+			runtime.balances.transfer(alice.clone(), bob.clone(), 50),
+			Err(Error::<TestRuntime>::InsufficientBalance.into())
+		);
+
+		// Reserved funds keep the account alive, even when all of its free balance is sent.
+		assert_eq!(
+			super::Pallet::<TestRuntime>::transfer_allow_death(
+				&mut runtime,
+				alice.clone(),
+				bob.clone(),
+				40
+			),
+			Ok(())
+		);
+		assert_eq!(runtime.balances.balance(&alice), 0);
+		assert_eq!(runtime.balances.reserved_balance(&alice), 60);
+
+		// Only what is reserved can be unreserved.
+		assert_eq!(super::Pallet::<TestRuntime>::unreserve(&mut runtime, &alice, 20), 0);
+		assert_eq!(super::Pallet::<TestRuntime>::unreserve(&mut runtime, &alice, 50), 10);
+		assert_eq!(runtime.balances.balance(&alice), 60);
+		assert_eq!(runtime.balances.reserved_balance(&alice), 0);
+		assert!(!runtime.balances.reserved.contains_key(&alice));
+		assert_eq!(runtime.balances.try_state(0), Ok(()));
+	}
+
+	#[test]
+	fn slash_reserved() {
+		let mut runtime = new_test_runtime();
+		let alice = "alice".to_string();
+		runtime.balances.set_balance(&alice, 100);
+		assert_eq!(runtime.balances.reserve(&alice, 30), Ok(()));
+
+		// Slashed funds are destroyed.
+		assert_eq!(super::Pallet::<TestRuntime>::slash_reserved(&mut runtime, &alice, 10), 0);
+		assert_eq!(runtime.balances.reserved_balance(&alice), 20);
+		assert_eq!(runtime.balances.total_issuance(), 90);
+		assert_eq!(super::Pallet::<TestRuntime>::slash_reserved(&mut runtime, &alice, 50), 30);
+		assert_eq!(runtime.balances.reserved_balance(&alice), 0);
+		assert_eq!(runtime.balances.balance(&alice), 70);
+		assert_eq!(runtime.balances.total_issuance(), 70);
+		assert_eq!(runtime.balances.try_state(0), Ok(()));
+	}
+
+	#[test]
+	fn accounts_are_reaped_once_their_reserve_is_gone() {
+		let mut runtime = new_test_runtime();
+		let (alice, bob) = ("alice".to_string(), "bob".to_string());
+		runtime.balances.set_balance(&alice, 100);
+		runtime.balances.set_balance(&bob, 100);
+		for _ in 0..3 {
+			runtime.system.inc_nonce(&alice);
+			runtime.system.inc_nonce(&bob);
+		}
+
+		// Slashing the whole reserve leaves alice with dust, so the account is reaped.
+		assert_eq!(runtime.balances.reserve(&alice, 98), Ok(()));
+		assert_eq!(super::Pallet::<TestRuntime>::slash_reserved(&mut runtime, &alice, 98), 0);
+		assert!(!runtime.balances.balances.contains_key(&alice));
+		assert_eq!(runtime.system.nonce(&alice), 0);
+		assert_eq!(runtime.balances.total_issuance(), 100);
+
+		// Unreserving the rest of the reserve can leave bob with dust too.
+		assert_eq!(runtime.balances.reserve(&bob, 98), Ok(()));
+		assert_eq!(super::Pallet::<TestRuntime>::slash_reserved(&mut runtime, &bob, 96), 0);
+		assert_eq!(runtime.system.nonce(&bob), 3);
+		assert_eq!(super::Pallet::<TestRuntime>::unreserve(&mut runtime, &bob, 2), 0);
+		assert!(!runtime.balances.balances.contains_key(&bob));
+		assert_eq!(runtime.system.nonce(&bob), 0);
+		assert_eq!(runtime.balances.total_issuance(), 0);
+		assert_eq!(runtime.balances.try_state(0), Ok(()));
+	}
+
+	#[test]
+	fn repatriate_reserved() {
+		let mut runtime = new_test_runtime();
+		let (alice, bob, charlie) = ("alice".to_string(), "bob".to_string(), "charlie".to_string());
+		runtime.balances.set_balance(&alice, 100);
+		runtime.balances.set_balance(&bob, 10);
+		assert_eq!(runtime.balances.reserve(&alice, 50), Ok(()));
+		let repatriate = |runtime: &mut TestRuntime, from: &String, to: &String, amount, status| {
+			super::Pallet::<TestRuntime>::repatriate_reserved(runtime, from, to, amount, status)
+		};
+
+		// The beneficiary must exist.
+		assert_eq!(
+			repatriate(&mut runtime, &alice, &charlie, 10, BalanceStatus::Free),
+			Err(Error::<TestRuntime>::DeadAccount.into())
+		);
+
+		assert_eq!(repatriate(&mut runtime, &alice, &bob, 10, BalanceStatus::Free), Ok(0));
+		assert_eq!(runtime.balances.balance(&bob), 20);
+		assert_eq!(repatriate(&mut runtime, &alice, &bob, 15, BalanceStatus::Reserved), Ok(0));
+		assert_eq!(runtime.balances.reserved_balance(&bob), 15);
+		assert_eq!(repatriate(&mut runtime, &alice, &bob, 40, BalanceStatus::Free), Ok(15));
+		assert_eq!(runtime.balances.balance(&bob), 45);
+		assert_eq!(runtime.balances.reserved_balance(&alice), 0);
+		assert_eq!(runtime.balances.balance(&alice), 50);
+
+		// Repatriating to the same account only moves funds between free and reserved.
+		assert_eq!(repatriate(&mut runtime, &bob, &bob, 5, BalanceStatus::Free), Ok(0));
+		assert_eq!(runtime.balances.balance(&bob), 50);
+		assert_eq!(runtime.balances.reserved_balance(&bob), 10);
+
+		// An account left with only dust once its reserve is moved away is reaped.
+		runtime.balances.set_balance(&charlie, 10);
+		assert_eq!(runtime.balances.reserve(&charlie, 8), Ok(()));
+		assert_eq!(repatriate(&mut runtime, &charlie, &bob, 8, BalanceStatus::Free), Ok(0));
+		assert!(!runtime.balances.balances.contains_key(&charlie));
+		assert_eq!(runtime.balances.balance(&bob), 58);
+
+		assert_eq!(runtime.balances.total_issuance(), 118);
+		assert_eq!(runtime.balances.try_state(0), Ok(()));
+	}
+
+	#[test]
+	fn overlapping_locks() {
+		const VESTING: [u8; 8] = *b"vesting ";
+		const STAKING: [u8; 8] = *b"staking ";
+
+		let mut balances = super::Pallet::<TestRuntime>::new();
+		let (alice, bob) = ("alice".to_string(), "bob".to_string());
+		balances.set_balance(&alice, 100);
+
+		// Locks from different identifiers overlap, so only the largest one counts.
+		balances.set_lock(VESTING, &alice, 30);
+		balances.set_lock(STAKING, &alice, 50);
+		assert_eq!(balances.frozen_balance(&alice), 50);
+		assert_eq!(
+			balances.transfer(alice.clone(), bob.clone(), 51),
+			Err(Error::<TestRuntime>::LiquidityRestrictions.into())
+		);
+		assert_eq!(
+			balances.reserve(&alice, 51),
+			Err(Error::<TestRuntime>::LiquidityRestrictions.into())
+		);
+		assert!(!balances.can_reserve(&alice, 51));
+		assert_eq!(balances.transfer(alice.clone(), bob.clone(), 50), Ok(()));
+		assert_eq!(balances.balance(&alice), 50);
+
+		// Removing one lock leaves the others in place.
+		balances.remove_lock(STAKING, &alice);
+		assert_eq!(balances.frozen_balance(&alice), 30);
+		assert_eq!(
+			balances.transfer(alice.clone(), bob.clone(), 21),
+			Err(Error::<TestRuntime>::LiquidityRestrictions.into())
+		);
+
+		// Extending a lock never makes it smaller, but setting a lock replaces it.
+		balances.extend_lock(VESTING, &alice, 20);
+		assert_eq!(balances.frozen_balance(&alice), 30);
+		balances.extend_lock(VESTING, &alice, 40);
+		assert_eq!(balances.frozen_balance(&alice), 40);
+		balances.set_lock(VESTING, &alice, 10);
+		assert_eq!(balances.frozen_balance(&alice), 10);
+		assert_eq!(balances.transfer(alice.clone(), bob.clone(), 40), Ok(()));
+
+		// Locking zero removes the lock.
+		balances.set_lock(VESTING, &alice, 0);
+		assert_eq!(balances.frozen_balance(&alice), 0);
+		assert!(!balances.locks.contains_key(&alice));
+		assert_eq!(balances.try_state(0), Ok(()));
+	}
+
+	#[test]
+	fn locked_accounts_are_not_reaped() {
+		const VESTING: [u8; 8] = *b"vesting ";
+
+		let mut runtime = new_test_runtime();
+		let (alice, bob, root) = ("alice".to_string(), "bob".to_string(), "root".to_string());
+		runtime.balances.set_balance(&alice, 10);
+		runtime.balances.set_balance(&bob, 10);
+		// The lock is below the existential deposit, so the account could otherwise be reaped.
+		runtime.balances.set_lock(VESTING, &alice, 3);
+
+		assert_eq!(
+			super::Pallet::<TestRuntime>::transfer_allow_death(
+				&mut runtime,
+				alice.clone(),
+				bob.clone(),
+				7
+			),
+			Err(Error::<TestRuntime>::LiquidityRestrictions.into())
+		);
+		assert_eq!(
+			super::Pallet::<TestRuntime>::burn(&mut runtime, root.clone(), alice.clone(), 7),
+			Err(Error::<TestRuntime>::LiquidityRestrictions.into())
+		);
+		assert_eq!(runtime.balances.balance(&alice), 10);
+		assert_eq!(runtime.balances.frozen_balance(&alice), 3);
+
+		// Once the lock is removed, the account can be reaped.
+		runtime.balances.remove_lock(VESTING, &alice);
+		assert_eq!(
+			super::Pallet::<TestRuntime>::burn(&mut runtime, root, alice.clone(), 7),
+			Ok(())
+		);
+		assert_eq!(runtime.balances.balance(&alice), 0);
+		assert_eq!(runtime.balances.total_issuance(), 10);
+	}
+
+	#[test]
+	fn accounts_which_do_not_exist_are_not_reaped() {
+		let mut runtime = new_test_runtime();
+		let (alice, dave, root) = ("alice".to_string(), "dave".to_string(), "root".to_string());
+		runtime.balances.set_balance(&alice, 10);
+		for _ in 0..3 {
+			runtime.system.inc_nonce(&dave);
+		}
+
+		// Dave has no balance, so moving or burning nothing from the account changes nothing.
+		assert_eq!(
+			super::Pallet::<TestRuntime>::transfer_allow_death(
+				&mut runtime,
+				dave.clone(),
+				alice.clone(),
+				0
+			),
+			Ok(())
+		);
+		assert_eq!(super::Pallet::<TestRuntime>::burn(&mut runtime, root, dave.clone(), 0), Ok(()));
+		assert_eq!(runtime.system.nonce(&dave), 3);
+		assert!(!runtime.balances.balances.contains_key(&dave));
+		assert_eq!(runtime.balances.balance(&alice), 10);
+		assert_eq!(runtime.balances.try_state(0), Ok(()));
+	}
+
+	#[test]
+	fn allowances() {
+		let mut balances = super::Pallet::<TestRuntime>::new();
+		let (alice, bob, charlie) = ("alice".to_string(), "bob".to_string(), "charlie".to_string());
+		balances.set_balance(&alice, 100);
+
+		// Nothing can be transferred without an allowance.
+		assert_eq!(
+			balances.transfer_from(bob.clone(), alice.clone(), charlie.clone(), 10),
+			Err(Error::<TestRuntime>::InsufficientAllowance.into())
+		);
+
+		assert_eq!(balances.approve(alice.clone(), bob.clone(), 30), Ok(()));
+		assert_eq!(balances.allowance(&alice, &bob), 30);
+		assert_eq!(balances.allowance(&bob, &alice), 0);
+		assert_eq!(balances.transfer_from(bob.clone(), alice.clone(), charlie.clone(), 10), Ok(()));
+		assert_eq!(balances.balance(&alice), 90);
+		assert_eq!(balances.balance(&charlie), 10);
+		assert_eq!(balances.allowance(&alice, &bob), 20);
+		assert_eq!(
+			balances.transfer_from(bob.clone(), alice.clone(), charlie.clone(), 21),
+			Err(Error::<TestRuntime>::InsufficientAllowance.into())
+		);
+
+		// The owner must still have the funds, and the allowance is kept if the transfer fails.
+		balances.set_balance(&alice, 15);
+		assert_eq!(
+			balances.transfer_from(bob.clone(), alice.clone(), charlie.clone(), 20),
+			Err(Error::<TestRuntime>::InsufficientBalance.into())
+		);
+		assert_eq!(
+			balances.transfer_from(bob.clone(), alice.clone(), charlie.clone(), 15),
+			Err(Error::<TestRuntime>::KeepAlive.into())
+		);
+		assert_eq!(balances.allowance(&alice, &bob), 20);
+
+		// Approving again replaces the allowance.
+		assert_eq!(balances.approve(alice.clone(), bob.clone(), 5), Ok(()));
+		assert_eq!(balances.allowance(&alice, &bob), 5);
+		assert_eq!(balances.transfer_from(bob.clone(), alice.clone(), charlie.clone(), 5), Ok(()));
+		assert_eq!(balances.allowance(&alice, &bob), 0);
+		assert!(!balances.allowances.contains_key(&alice));
+		assert_eq!(balances.try_state(0), Ok(()));
+	}
+
+	#[test]
+	fn decrease_allowance() {
+		let mut balances = super::Pallet::<TestRuntime>::new();
+		let (alice, bob) = ("alice".to_string(), "bob".to_string());
+
+		assert_eq!(balances.approve(alice.clone(), bob.clone(), 30), Ok(()));
+		assert_eq!(balances.decrease_allowance(alice.clone(), bob.clone(), 10), Ok(()));
+		assert_eq!(balances.allowance(&alice, &bob), 20);
+		assert_eq!(
+			balances.decrease_allowance(alice.clone(), bob.clone(), 21),
+			Err(Error::<TestRuntime>::InsufficientAllowance.into())
+		);
+		assert_eq!(balances.decrease_allowance(alice.clone(), bob.clone(), 20), Ok(()));
+		assert_eq!(balances.allowance(&alice, &bob), 0);
+		assert!(balances.allowances.is_empty());
+	}
+
+	#[test]
+	fn fungible_currency() {
+		// Other pallets only know about the `Currency` trait, not this pallet.
+		fn pay_fee<C: Currency<TestRuntime, String, Balance = u128>>(
+			currency: &mut C,
+			who: &String,
+			fee: u128,
+		) -> Result<(), DispatchError> {
+			// The fee is destroyed, since it is not resolved into any account.
+			let _fee = currency.withdraw(who, fee)?;
+			Ok(())
+		}
+
+		let mut balances = super::Pallet::<TestRuntime>::new();
+		let (alice, bob) = ("alice".to_string(), "bob".to_string());
+		balances.set_balance(&alice, 100);
+		assert_eq!(balances.minimum_balance(), 5);
+
+		assert_eq!(pay_fee(&mut balances, &alice, 10), Ok(()));
+		assert_eq!(balances.free_balance(&alice), 90);
+		assert_eq!(Fungible::total_issuance(&balances), 90);
+		assert_eq!(balances.try_state(0), Ok(()));
+		// Accounts are never reaped through the trait.
+		assert_eq!(pay_fee(&mut balances, &alice, 90), Err(Error::<TestRuntime>::KeepAlive.into()));
+		assert_eq!(
+			Fungible::transfer(&mut balances, &alice, &bob, 90),
+			Err(Error::<TestRuntime>::KeepAlive.into())
+		);
+		assert_eq!(Fungible::transfer(&mut balances, &alice, &bob, 40), Ok(()));
+		assert_eq!(balances.free_balance(&bob), 40);
+
+		// New funds are counted in the total issuance as soon as they are deposited.
+		assert_eq!(balances.deposit(&bob, 20), Ok(()));
+		assert_eq!(balances.free_balance(&bob), 60);
+		assert_eq!(Fungible::total_issuance(&balances), 110);
+		assert_eq!(balances.try_state(0), Ok(()));
+		assert_eq!(
+			balances.deposit(&"charlie".to_string(), 4),
+			Err(Error::<TestRuntime>::ExistentialDeposit.into())
+		);
+
+		// Funds withdrawn from one account can be resolved into another, which moves them.
+		let taken = balances.withdraw(&bob, 15).unwrap();
+		assert_eq!(taken.peek(), 15);
+		assert_eq!(Fungible::total_issuance(&balances), 95);
+		assert_eq!(balances.resolve(&alice, taken), Ok(()));
+		assert_eq!(balances.free_balance(&alice), 65);
+		assert_eq!(balances.free_balance(&bob), 45);
+		assert_eq!(Fungible::total_issuance(&balances), 110);
+		assert_eq!(balances.try_state(0), Ok(()));
+
+		// The total issuance never goes below zero, even if it is wrong.
+		balances.total_issuance = 10;
+		assert_eq!(pay_fee(&mut balances, &alice, 20), Err(Error::<TestRuntime>::Underflow.into()));
+		assert_eq!(balances.free_balance(&alice), 65);
+	}
+
+	#[test]
+	fn instances_are_independent() {
+		let mut runtime = new_test_runtime();
+		runtime.balances.set_balance(&"alice".to_string(), 100);
+		runtime.loyalty.set_balance(&"alice".to_string(), 10);
+
+		// Loyalty points have no existential deposit, so they can all be sent.
+		let call = super::Call::<TestRuntime, Instance1>::transfer_allow_death {
+			to: "bob".to_string(),
+			amount: 10,
+		};
+		assert_eq!(
+			execute_next_block(&mut runtime, vec![("alice".to_string(), call.into())]),
+			Ok(())
+		);
+		assert_eq!(runtime.loyalty.balance(&"alice".to_string()), 0);
+		assert_eq!(runtime.loyalty.balance(&"bob".to_string()), 10);
+
+		// The default instance is not affected.
+		assert_eq!(runtime.balances.balance(&"alice".to_string()), 100);
+		assert_eq!(runtime.balances.balance(&"bob".to_string()), 0);
+	}
+
+	#[test]
+	fn encode_transfer_call() {
+		let call = super::Call::<TestRuntime>::transfer { to: "bob".to_string(), amount: 5 };
+		let encoded = call.encode();
+
+		// The call index, then the length of "bob" and its bytes, then the amount.
+		let mut expected = vec![0, 3, 0, 0, 0, b'b', b'o', b'b'];
+		expected.extend_from_slice(&5u128.to_le_bytes());
+		assert_eq!(encoded, expected);
+
+		let decoded = super::Call::<TestRuntime>::decode(&mut &encoded[..]).unwrap();
+		assert_eq!(decoded, call);
+		assert!(super::Call::<TestRuntime>::decode(&mut &[1u8][..]).is_err());
+	}
+
+	#[test]
+	fn parse_transfer_call() {
+		use crate::support::ParseCallError;
+
+		let call = "transfer to=bob amount=5".parse::<super::Call<TestRuntime>>();
+		assert_eq!(call, Ok(super::Call::transfer { to: "bob".to_string(), amount: 5 }));
+
+		// Arguments can be given in any order, and values can be quoted.
+		let call = r#"transfer amount=5 to="bob smith""#.parse::<super::Call<TestRuntime>>();
+		assert_eq!(call, Ok(super::Call::transfer { to: "bob smith".to_string(), amount: 5 }));
+
+		let parse = |s: &str| s.parse::<super::Call<TestRuntime>>();
+		assert_eq!(
+			parse("mint_all to=bob"),
+			Err(ParseCallError::UnknownCall("mint_all".to_string()))
+		);
+		assert_eq!(parse("transfer to=bob"), Err(ParseCallError::MissingArgument("amount")));
+		assert_eq!(
+			parse("transfer to=bob amount=lots"),
+			Err(ParseCallError::InvalidArgument { name: "amount", value: "lots".to_string() })
+		);
+		assert_eq!(
+			parse("transfer to=bob amount=5 fee=1"),
+			Err(ParseCallError::UnknownArgument("fee".to_string()))
+		);
+		assert_eq!(
+			parse("transfer to=bob to=charlie amount=5"),
+			Err(ParseCallError::DuplicateArgument("to".to_string()))
+		);
+		assert_eq!(
+			parse("transfer bob 5"),
+			Err(ParseCallError::InvalidFormat("expected arguments like `name=value`"))
+		);
+	}
+
+	#[test]
+	fn call_traits() {
+		let call = super::Call::<TestRuntime>::transfer { to: "bob".to_string(), amount: 5 };
+		let retry = call.clone();
+
+		assert_eq!(call, retry);
+		assert_ne!(call, super::Call::<TestRuntime>::transfer { to: "bob".to_string(), amount: 6 });
+		assert_eq!(format!("{call:?}"), r#"transfer { to: "bob", amount: 5 }"#);
+		assert_eq!(call.call_name(), "transfer");
 	}
 }
@@ -1,9 +1,14 @@
+mod assets;
 mod balances;
+#[cfg(test)]
+mod mock;
 mod proof_of_existence;
 mod support;
 mod system;
+mod tx_pause;
+mod vesting;
 
-use crate::support::Dispatch;
+use crate::support::{Decode, Encode, Fungible, Hash, IsSubType};
 
 // These are the concrete types we will use in our simple state machine.
 // Modules are configured for these types directly, and they satisfy all of our
@@ -11,38 +16,141 @@ use crate::support::Dispatch;
 mod types {
 	pub type AccountId = String;
 	pub type Balance = u128;
+	pub type LoyaltyPoints = u64;
 	pub type BlockNumber = u32;
 	pub type Nonce = u32;
 	pub type Extrinsic = crate::support::Extrinsic<AccountId, crate::RuntimeCall>;
 	pub type Header = crate::support::Header<BlockNumber>;
 	pub type Block = crate::support::Block<Header, Extrinsic>;
-	pub type Content = String;
+	pub type Content = crate::support::H256;
+	pub type AssetId = u32;
+}
+
+// These are the constant parameters of our pallets.
+support::parameter_types! {
+	pub RootAccount: types::AccountId = "root".to_string();
+	// Accounts with less than this many native tokens are reaped.
+	pub ExistentialDeposit: types::Balance = 5;
+	// Loyalty point accounts are never reaped.
+	pub LoyaltyExistentialDeposit: types::LoyaltyPoints = 0;
+	pub SafeModeDuration: u32 = 2;
+	// Native token transfers keep working during safe mode.
+	pub SafeModeWhitelist: Vec<(&'static str, &'static str)> = vec![("balances", "transfer")];
+	pub MinVestedTransfer: types::Balance = 10;
+	pub MaxVestingSchedules: u32 = 3;
+	// Every claim reserves this many native tokens from its owner.
+	pub ClaimDeposit: types::Balance = 5;
+	pub MaxClaimsPerAccount: u32 = 10;
+	// Claims expire after this many blocks, unless their owner pays to renew them.
+	pub ClaimDuration: Option<types::BlockNumber> = Some(10);
+	pub RenewalFee: types::Balance = 1;
+	pub MaxExpiriesPerBlock: u32 = 5;
+	// Creating an asset reserves this many native tokens.
+	pub AssetDeposit: types::Balance = 10;
+	pub AssetStringLimit: u32 = 16;
 }
 
 // This is our main Runtime.
 // It accumulates all of the different pallets we want to use,
 // functions implemented on the Runtime allow us to access those pallets and execute blocks of
 // transactions.
+//
+// After every block, the runtime checks the invariants of each pallet, like the total issuance of
+// the balances pallet.
 #[derive(Debug)]
-#[macros::runtime]
+#[macros::runtime(try_state)]
 pub struct Runtime {
+	#[pallet_index(0)]
 	system: system::Pallet<Self>,
+	#[pallet_index(1)]
 	balances: balances::Pallet<Self>,
+	#[pallet_index(2)]
 	proof_of_existence: proof_of_existence::Pallet<Self>,
+	// A second instance of the balances pallet, which keeps track of loyalty points.
+	#[pallet_index(3)]
+	loyalty: balances::Pallet<Self, support::Instance1>,
+	#[pallet_index(4)]
+	tx_pause: tx_pause::Pallet<Self>,
+	#[pallet_index(5)]
+	vesting: vesting::Pallet<Self>,
+	#[pallet_index(6)]
+	assets: assets::Pallet<Self>,
+}
+
+// These are all the events which can be emitted in our runtime, by the pallets which have events.
+// Each pallet deposits its events in the system pallet, which converts them into this enum.
+#[derive(Debug)]
+pub enum RuntimeEvent {
+	System(system::Event<Runtime>),
+	ProofOfExistence(proof_of_existence::Event<Runtime>),
+}
+
+impl From<system::Event<Runtime>> for RuntimeEvent {
+	fn from(event: system::Event<Runtime>) -> Self {
+		Self::System(event)
+	}
+}
+
+impl From<proof_of_existence::Event<Runtime>> for RuntimeEvent {
+	fn from(event: proof_of_existence::Event<Runtime>) -> Self {
+		Self::ProofOfExistence(event)
+	}
 }
 
 impl system::Config for Runtime {
 	type AccountId = types::AccountId;
 	type BlockNumber = types::BlockNumber;
 	type Nonce = types::Nonce;
+	type PalletInfo = Self;
+	type RuntimeCall = RuntimeCall;
+	// Root can pause pallets and calls, or enter safe mode, through the `tx_pause` pallet.
+	type BaseCallFilter = tx_pause::Pallet<Self>;
+	type RootAccount = RootAccount;
+	type RuntimeEvent = RuntimeEvent;
 }
 
 impl balances::Config for Runtime {
 	type Balance = types::Balance;
+	type ExistentialDeposit = ExistentialDeposit;
+	// A reaped account also loses its nonce in the system pallet.
+	type OnKilledAccount = system::Pallet<Self>;
+}
+
+impl balances::Config<support::Instance1> for Runtime {
+	type Balance = types::LoyaltyPoints;
+	type ExistentialDeposit = LoyaltyExistentialDeposit;
+	type OnKilledAccount = ();
 }
 
 impl proof_of_existence::Config for Runtime {
 	type Content = types::Content;
+	type Hashing = support::Sha256;
+	type Currency = balances::Pallet<Self>;
+	type ClaimDeposit = ClaimDeposit;
+	type MaxClaimsPerAccount = MaxClaimsPerAccount;
+	type ClaimDuration = ClaimDuration;
+	type RenewalFee = RenewalFee;
+	type MaxExpiriesPerBlock = MaxExpiriesPerBlock;
+}
+
+impl tx_pause::Config for Runtime {
+	type SafeModeDuration = SafeModeDuration;
+	type SafeModeWhitelist = SafeModeWhitelist;
+}
+
+impl vesting::Config for Runtime {
+	type Balance = types::Balance;
+	type Currency = balances::Pallet<Self>;
+	type MinVestedTransfer = MinVestedTransfer;
+	type MaxVestingSchedules = MaxVestingSchedules;
+}
+
+impl assets::Config for Runtime {
+	type AssetId = types::AssetId;
+	type Balance = types::Balance;
+	type Currency = balances::Pallet<Self>;
+	type AssetDeposit = AssetDeposit;
+	type StringLimit = AssetStringLimit;
 }
 
 // The main entry point for our simple state machine.
@@ -53,9 +161,14 @@ fn main() {
 	let alice = "alice".to_string();
 	let bob = "bob".to_string();
 	let charlie = "charlie".to_string();
+	let dave = "dave".to_string();
+	// Claims are made on the hash of some content, rather than the content itself.
+	let hello = support::Sha256::hash(b"Hello, world!");
+	let root = "root".to_string();
 
 	// Initialize the system with some initial balance.
 	runtime.balances.set_balance(&alice, 100);
+	runtime.loyalty.set_balance(&alice, 10);
 
 	// Here are the extrinsics in our block.
 	// You can add or remove these based on the modules and calls you have set up.
@@ -71,7 +184,18 @@ fn main() {
 			},
 			support::Extrinsic {
 				caller: alice.clone(),
-				call: RuntimeCall::balances(balances::Call::transfer { to: charlie, amount: 20 }),
+				call: RuntimeCall::balances(balances::Call::transfer {
+					to: charlie.clone(),
+					amount: 20,
+				}),
+			},
+			// This transfers loyalty points, which does not affect the native token balances.
+			support::Extrinsic {
+				caller: alice.clone(),
+				call: RuntimeCall::loyalty(balances::Call::transfer {
+					to: charlie.clone(),
+					amount: 5,
+				}),
 			},
 		],
 	};
@@ -79,44 +203,248 @@ fn main() {
 	let block_2 = types::Block {
 		header: support::Header { block_number: 2 },
 		extrinsics: vec![
+			// Pallet calls can also be converted into a `RuntimeCall` with `into`.
 			support::Extrinsic {
 				caller: alice.clone(),
-				call: RuntimeCall::proof_of_existence(proof_of_existence::Call::create_claim {
-					claim: "Hello, world!".to_string(),
-				}),
+				call: proof_of_existence::Call::create_claim_from_bytes {
+					data: "Hello, world!".into(),
+				}
+				.into(),
 			},
 			support::Extrinsic {
 				caller: bob.clone(),
 				call: RuntimeCall::proof_of_existence(proof_of_existence::Call::create_claim {
-					claim: "Hello, world!".to_string(),
+					claim: hello,
 				}),
 			},
 		],
 	};
 
+	// Calls are sent to the blockchain as bytes, which the runtime decodes back into a
+	// `RuntimeCall`. Thanks to explicit call and pallet indices, these bytes stay the same even if
+	// we reorder our pallets or their functions.
+	let encoded_call =
+		RuntimeCall::proof_of_existence(proof_of_existence::Call::create_claim { claim: hello })
+			.encode();
+	let decoded_call = RuntimeCall::decode(&mut &encoded_call[..]).expect("invalid call");
+
+	// Calls can also be written as strings, like an operator would type them, and parsed into a
+	// `RuntimeCall`.
+	let parsed_call: RuntimeCall =
+		"loyalty.transfer to=bob amount=2".parse().expect("invalid call");
+
 	let block_3 = types::Block {
 		header: support::Header { block_number: 3 },
 		extrinsics: vec![
+			support::Extrinsic { caller: alice.clone(), call: parsed_call },
 			support::Extrinsic {
-				caller: alice,
+				caller: alice.clone(),
 				call: RuntimeCall::proof_of_existence(proof_of_existence::Call::revoke_claim {
-					claim: "Hello, world!".to_string(),
+					claim: hello,
+				}),
+			},
+			support::Extrinsic { caller: bob.clone(), call: decoded_call },
+		],
+	};
+
+	// Root can pause a call. Until it is unpaused, the call fails without being dispatched.
+	// The system pallet is callable too: root can write raw storage, and anyone can anchor some
+	// data on chain with a remark.
+	let block_4 = types::Block {
+		header: support::Header { block_number: 4 },
+		extrinsics: vec![
+			support::Extrinsic {
+				caller: root.clone(),
+				call: RuntimeCall::system(system::Call::set_storage {
+					key: "motd".into(),
+					value: "Hello, world!".into(),
 				}),
 			},
 			support::Extrinsic {
-				caller: bob,
+				caller: charlie.clone(),
+				call: system::Call::remark_with_event { data: "0xdeadbeef".parse().unwrap() }
+					.into(),
+			},
+			support::Extrinsic {
+				caller: root.clone(),
+				call: "tx_pause.pause pallet=proof_of_existence call=create_claim"
+					.parse()
+					.expect("invalid call"),
+			},
+			support::Extrinsic {
+				caller: bob.clone(),
 				call: RuntimeCall::proof_of_existence(proof_of_existence::Call::create_claim {
-					claim: "Hello, world!".to_string(),
+					claim: support::Sha256::hash(b"Paused claim"),
 				}),
 			},
 		],
 	};
 
+	// An account which would be left with less than the existential deposit is kept alive by
+	// `transfer`, but reaped by `transfer_allow_death`, which also removes its nonce. Root can
+	// also create new tokens, and alice allows bob to spend some of their tokens for them.
+	let block_5 = types::Block {
+		header: support::Header { block_number: 5 },
+		extrinsics: vec![
+			support::Extrinsic {
+				caller: charlie.clone(),
+				call: RuntimeCall::balances(balances::Call::transfer {
+					to: alice.clone(),
+					amount: 18,
+				}),
+			},
+			support::Extrinsic {
+				caller: charlie.clone(),
+				call: RuntimeCall::balances(balances::Call::transfer_allow_death {
+					to: alice.clone(),
+					amount: 18,
+				}),
+			},
+			support::Extrinsic {
+				caller: root,
+				call: RuntimeCall::balances(balances::Call::mint {
+					to: charlie.clone(),
+					amount: 50,
+				}),
+			},
+			support::Extrinsic {
+				caller: alice.clone(),
+				call: RuntimeCall::balances(balances::Call::approve {
+					spender: bob.clone(),
+					amount: 20,
+				}),
+			},
+			support::Extrinsic {
+				caller: bob.clone(),
+				call: RuntimeCall::balances(balances::Call::transfer_from {
+					owner: alice.clone(),
+					to: charlie.clone(),
+					amount: 15,
+				}),
+			},
+		],
+	};
+
+	// Alice sends dave some tokens which are released over a few blocks. Until they have vested,
+	// and dave calls `vest`, they are locked and cannot be transferred.
+	let block_6 = types::Block {
+		header: support::Header { block_number: 6 },
+		extrinsics: vec![
+			support::Extrinsic {
+				caller: alice.clone(),
+				call: "vesting.vested_transfer target=dave schedule=30,10,6"
+					.parse()
+					.expect("invalid call"),
+			},
+			support::Extrinsic {
+				caller: dave.clone(),
+				call: RuntimeCall::balances(balances::Call::transfer {
+					to: alice.clone(),
+					amount: 10,
+				}),
+			},
+		],
+	};
+
+	let block_7 = types::Block {
+		header: support::Header { block_number: 7 },
+		extrinsics: vec![
+			support::Extrinsic { caller: dave.clone(), call: vesting::Call::vest {}.into() },
+			support::Extrinsic {
+				caller: dave.clone(),
+				call: RuntimeCall::balances(balances::Call::transfer {
+					to: alice.clone(),
+					amount: 10,
+				}),
+			},
+		],
+	};
+
+	// Alice creates their own token, and pays some of it to bob, who passes some on to charlie.
+	let block_8 = types::Block {
+		header: support::Header { block_number: 8 },
+		extrinsics: vec![
+			support::Extrinsic {
+				caller: alice.clone(),
+				call: assets::Call::create { id: 1, min_balance: 1 }.into(),
+			},
+			support::Extrinsic {
+				caller: alice.clone(),
+				call: "assets.set_metadata id=1 name=Alicoin symbol=ALC decimals=2"
+					.parse()
+					.expect("invalid call"),
+			},
+			support::Extrinsic {
+				caller: alice.clone(),
+				call: assets::Call::mint { id: 1, beneficiary: bob.clone(), amount: 100 }.into(),
+			},
+			support::Extrinsic {
+				caller: bob.clone(),
+				call: assets::Call::transfer { id: 1, target: charlie.clone(), amount: 40 }.into(),
+			},
+		],
+	};
+
+	// Bob offers their claim to charlie, who accepts it and takes over its deposit. Charlie then
+	// pays to renew the claim, so it does not expire.
+	let block_9 = types::Block {
+		header: support::Header { block_number: 9 },
+		extrinsics: vec![
+			support::Extrinsic {
+				caller: bob.clone(),
+				call: proof_of_existence::Call::transfer_claim {
+					claim: hello,
+					new_owner: charlie.clone(),
+				}
+				.into(),
+			},
+			support::Extrinsic {
+				caller: charlie.clone(),
+				call: proof_of_existence::Call::accept_claim { claim: hello }.into(),
+			},
+			support::Extrinsic {
+				caller: charlie.clone(),
+				call: proof_of_existence::Call::renew_claim { claim: hello }.into(),
+			},
+		],
+	};
+
+	// We can look inside each `RuntimeCall` to find the calls to a specific pallet.
+	let native_transfers = block_1
+		.extrinsics
+		.iter()
+		.filter(|ext| IsSubType::<balances::Call<Runtime>>::is_sub_type(&ext.call).is_some())
+		.count();
+	println!("Block 1 contains {native_transfers} native token transfers");
+
 	// Execute the extrinsics which make up our blocks.
 	// If there are any errors, our system panics, since we should not execute invalid blocks.
 	runtime.execute_block(block_1).expect("invalid block");
 	runtime.execute_block(block_2).expect("invalid block");
 	runtime.execute_block(block_3).expect("invalid block");
+	runtime.execute_block(block_4).expect("invalid block");
+	println!("Message of the day: {:?}", runtime.system.storage(&"motd".into()));
+	println!("Events in block 4: {:?}", runtime.system.events());
+	runtime.execute_block(block_5).expect("invalid block");
+	println!("Nonce of charlie after being reaped: {}", runtime.system.nonce(&charlie));
+	println!("Allowance of bob from alice: {}", runtime.balances.allowance(&alice, &bob));
+	println!("Total issuance: {}", runtime.balances.total_issuance());
+	runtime.execute_block(block_6).expect("invalid block");
+	runtime.execute_block(block_7).expect("invalid block");
+	println!("Vesting schedules of dave: {:?}", runtime.vesting.vesting(&dave));
+	runtime.execute_block(block_8).expect("invalid block");
+	let alicoin = runtime.assets.fungible(1);
+	println!(
+		"Alicoin of charlie: {} of {}",
+		alicoin.free_balance(&charlie),
+		alicoin.total_issuance()
+	);
+	runtime.execute_block(block_9).expect("invalid block");
+	println!("Claims of charlie: {:?}", runtime.proof_of_existence.claims_of(&charlie, 0, 10));
+	println!(
+		"Claim of \"Hello, world!\": {:?}",
+		runtime.proof_of_existence.verify(b"Hello, world!")
+	);
 
 	// Simply print the debug format of our runtime state.
 	println!("{runtime:#?}");
@@ -0,0 +1,159 @@
+use crate::{assets, balances, proof_of_existence, support, system, tx_pause, vesting};
+
+// These are the types used by the test runtime. They are kept small and readable, like using
+// `String` for accounts.
+mod types {
+	pub type AccountId = String;
+	pub type Balance = u128;
+	pub type LoyaltyPoints = u64;
+	pub type BlockNumber = u32;
+	pub type Nonce = u32;
+	pub type Extrinsic = crate::support::Extrinsic<AccountId, super::RuntimeCall>;
+	pub type Header = crate::support::Header<BlockNumber>;
+	pub type Block = crate::support::Block<Header, Extrinsic>;
+	pub type AssetId = u32;
+	pub type AssetBalance = u64;
+}
+
+// These are the constant parameters of the pallets in the test runtime.
+support::parameter_types! {
+	pub RootAccount: types::AccountId = "root".to_string();
+	pub ExistentialDeposit: types::Balance = 5;
+	pub LoyaltyExistentialDeposit: types::LoyaltyPoints = 0;
+	pub SafeModeDuration: u32 = 2;
+	pub SafeModeWhitelist: Vec<(&'static str, &'static str)> = vec![("balances", "transfer")];
+	pub MinVestedTransfer: types::Balance = 10;
+	pub MaxVestingSchedules: u32 = 2;
+	pub ClaimDeposit: types::Balance = 5;
+	pub MaxClaimsPerAccount: u32 = 3;
+	pub ClaimDuration: Option<types::BlockNumber> = Some(5);
+	pub RenewalFee: types::Balance = 1;
+	pub MaxExpiriesPerBlock: u32 = 2;
+	pub AssetDeposit: types::Balance = 10;
+	pub AssetStringLimit: u32 = 4;
+}
+
+// Claims in the tests are easier to read as plain strings, so this "hash" keeps the data as it is.
+pub struct Utf8;
+
+impl support::Hash for Utf8 {
+	type Output = String;
+
+	fn hash(data: &[u8]) -> String {
+		String::from_utf8_lossy(data).into_owned()
+	}
+}
+
+// A runtime which includes every pallet, and is shared by the tests of each pallet. Like the main
+// `Runtime`, calls are filtered by the `tx_pause` pallet.
+#[derive(Debug)]
+#[macros::runtime]
+pub struct TestRuntime {
+	#[pallet_index(0)]
+	pub system: system::Pallet<Self>,
+	#[pallet_index(1)]
+	pub balances: balances::Pallet<Self>,
+	#[pallet_index(2)]
+	pub proof_of_existence: proof_of_existence::Pallet<Self>,
+	#[pallet_index(3)]
+	pub loyalty: balances::Pallet<Self, support::Instance1>,
+	#[pallet_index(4)]
+	pub tx_pause: tx_pause::Pallet<Self>,
+	#[pallet_index(5)]
+	pub vesting: vesting::Pallet<Self>,
+	#[pallet_index(6)]
+	pub assets: assets::Pallet<Self>,
+}
+
+// The events of the pallets in the test runtime, like the `RuntimeEvent` of the main `Runtime`.
+#[derive(Debug)]
+pub enum RuntimeEvent {
+	System(system::Event<TestRuntime>),
+	ProofOfExistence(proof_of_existence::Event<TestRuntime>),
+}
+
+impl From<system::Event<TestRuntime>> for RuntimeEvent {
+	fn from(event: system::Event<TestRuntime>) -> Self {
+		Self::System(event)
+	}
+}
+
+impl From<proof_of_existence::Event<TestRuntime>> for RuntimeEvent {
+	fn from(event: proof_of_existence::Event<TestRuntime>) -> Self {
+		Self::ProofOfExistence(event)
+	}
+}
+
+impl system::Config for TestRuntime {
+	type AccountId = types::AccountId;
+	type BlockNumber = types::BlockNumber;
+	type Nonce = types::Nonce;
+	type PalletInfo = Self;
+	type RuntimeCall = RuntimeCall;
+	type BaseCallFilter = tx_pause::Pallet<Self>;
+	type RootAccount = RootAccount;
+	type RuntimeEvent = RuntimeEvent;
+}
+
+impl balances::Config for TestRuntime {
+	type Balance = types::Balance;
+	type ExistentialDeposit = ExistentialDeposit;
+	type OnKilledAccount = system::Pallet<Self>;
+}
+
+impl balances::Config<support::Instance1> for TestRuntime {
+	type Balance = types::LoyaltyPoints;
+	type ExistentialDeposit = LoyaltyExistentialDeposit;
+	type OnKilledAccount = ();
+}
+
+impl proof_of_existence::Config for TestRuntime {
+	type Content = String;
+	type Hashing = Utf8;
+	type Currency = balances::Pallet<Self>;
+	type ClaimDeposit = ClaimDeposit;
+	type MaxClaimsPerAccount = MaxClaimsPerAccount;
+	type ClaimDuration = ClaimDuration;
+	type RenewalFee = RenewalFee;
+	type MaxExpiriesPerBlock = MaxExpiriesPerBlock;
+}
+
+impl tx_pause::Config for TestRuntime {
+	type SafeModeDuration = SafeModeDuration;
+	type SafeModeWhitelist = SafeModeWhitelist;
+}
+
+impl vesting::Config for TestRuntime {
+	type Balance = types::Balance;
+	type Currency = balances::Pallet<Self>;
+	type MinVestedTransfer = MinVestedTransfer;
+	type MaxVestingSchedules = MaxVestingSchedules;
+}
+
+impl assets::Config for TestRuntime {
+	type AssetId = types::AssetId;
+	type Balance = types::AssetBalance;
+	type Currency = balances::Pallet<Self>;
+	type AssetDeposit = AssetDeposit;
+	type StringLimit = AssetStringLimit;
+}
+
+// Create a new test runtime, where every account starts out empty.
+pub fn new_test_runtime() -> TestRuntime {
+	TestRuntime::new()
+}
+
+// Execute the next block in the test runtime, which includes the given calls in order.
+pub fn execute_next_block(
+	runtime: &mut TestRuntime,
+	calls: Vec<(types::AccountId, RuntimeCall)>,
+) -> support::DispatchResult {
+	let block = types::Block {
+		header: support::Header { block_number: runtime.system.block_number() + 1 },
+		extrinsics: calls
+			.into_iter()
+			.map(|(caller, call)| support::Extrinsic { caller, call })
+			.collect(),
+	};
+	runtime.execute_block(block)
+}
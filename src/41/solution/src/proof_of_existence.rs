@@ -1,85 +1,960 @@
-use crate::support::DispatchResult;
+use crate::support::{
+	BalanceStatus, Bytes, DispatchResult, Fungible, Get, GetPallet, Hash, ReservableCurrency,
+};
 use core::fmt::Debug;
-use std::collections::BTreeMap;
+use num::traits::Zero;
+use std::collections::{BTreeMap, BTreeSet};
 
-pub trait Config: crate::system::Config {
+pub trait Config: crate::system::Config<BlockNumber: Ord, RuntimeEvent: From<Event<Self>>> {
 	/// The type which represents the content that can be claimed using this pallet.
 	/// Could be the content directly as bytes, or better yet the hash of that content.
 	/// We leave that decision to the runtime developer.
-	type Content: Debug + Ord;
+	type Content: Debug + Ord + Clone;
+	/// The hash function used to turn data into the `Content` which is claimed, so that claims
+	/// of large data only store a small digest.
+	type Hashing: Hash<Output = Self::Content>;
+	/// The currency used to pay the deposit for a claim.
+	type Currency: ReservableCurrency<Self, Self::AccountId, Balance: Copy + Debug + Ord + Zero>;
+	/// The amount reserved from the owner of a claim, so content cannot be claimed for free.
+	type ClaimDeposit: Get<BalanceOf<Self>>;
+	/// The maximum number of claims a single account can own.
+	type MaxClaimsPerAccount: Get<u32>;
+	/// The number of blocks a claim lasts before it expires, unless it is renewed.
+	/// If this is `None`, claims never expire.
+	type ClaimDuration: Get<Option<Self::BlockNumber>>;
+	/// The amount burned from the owner of a claim to renew it.
+	type RenewalFee: Get<BalanceOf<Self>>;
+	/// The maximum number of claims removed when they expire at the start of a block.
+	/// Any others are removed in the following blocks, so a mass expiry cannot overload a block.
+	type MaxExpiriesPerBlock: Get<u32>;
 }
 
+/// The balance of the `Currency` used to pay deposits.
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Fungible<<T as crate::system::Config>::AccountId>>::Balance;
+
+/// Information about a claim.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClaimInfo<AccountId, BlockNumber, Balance> {
+	/// The account which owns the claim.
+	pub owner: AccountId,
+	/// The block in which the content was claimed.
+	pub block_number: BlockNumber,
+	/// The amount reserved from the owner while the claim exists.
+	pub deposit: Balance,
+	/// The block at which the claim expires, if it ever does.
+	pub expires_at: Option<BlockNumber>,
+}
+
+/// Information about a claim of the Proof of Existence Module.
+pub type ClaimInfoOf<T> = ClaimInfo<
+	<T as crate::system::Config>::AccountId,
+	<T as crate::system::Config>::BlockNumber,
+	BalanceOf<T>,
+>;
+
 /// This is the Proof of Existence Module.
 /// It is a simple module that allows accounts to claim existence of some data.
-#[derive(Debug)]
 pub struct Pallet<T: Config> {
-	/// A simple storage map from content to the owner of that content.
+	/// A simple storage map from content to information about its claim.
 	/// Accounts can make multiple different claims, but each claim can only have one owner.
-	claims: BTreeMap<T::Content, T::AccountId>,
+	claims: BTreeMap<T::Content, ClaimInfoOf<T>>,
+	/// Claims which their owner has offered to another account, which must accept them.
+	pending_transfers: BTreeMap<T::Content, T::AccountId>,
+	/// The content claimed by each account, so we can list the claims of an account without
+	/// looking at every claim. This must always agree with `claims`.
+	claims_by_owner: BTreeMap<T::AccountId, BTreeSet<T::Content>>,
+	/// The claims which expire, ordered by the block they expire at.
+	expiries: BTreeSet<(T::BlockNumber, T::Content)>,
+	/// The current block number, as given to `on_initialize`. Claims which expire at or before
+	/// it are treated as expired, even if they have not been removed yet.
+	now: T::BlockNumber,
+}
+
+/// The events which can be emitted by the Proof of Existence Module.
+/// They are deposited in the System Pallet, as part of the `RuntimeEvent`.
+#[derive(Debug)]
+pub enum Event<T: Config + ?Sized> {
+	/// A claim expired, and was removed at the start of the block.
+	ClaimExpired { claim: T::Content, owner: T::AccountId },
+}
+
+// `#[derive(Debug)]` would not know that the claims need `T::BlockNumber: Debug`.
+impl<T: Config> Debug for Pallet<T>
+where
+	T::AccountId: Debug,
+	T::BlockNumber: Debug,
+{
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("Pallet")
+			.field("claims", &self.claims)
+			.field("pending_transfers", &self.pending_transfers)
+			.field("claims_by_owner", &self.claims_by_owner)
+			.field("expiries", &self.expiries)
+			.field("now", &self.now)
+			.finish()
+	}
 }
 
 impl<T: Config> Pallet<T> {
 	/// Create a new instance of the Proof of Existence Module.
 	pub fn new() -> Self {
-		Self { claims: BTreeMap::new() }
+		Self {
+			claims: BTreeMap::new(),
+			pending_transfers: BTreeMap::new(),
+			claims_by_owner: BTreeMap::new(),
+			expiries: BTreeSet::new(),
+			now: T::BlockNumber::zero(),
+		}
+	}
+
+	/// Get the information (if any) about a claim, including its owner.
+	/// A claim which has expired is not returned, even if it has not been removed yet.
+	pub fn get_claim(&self, claim: &T::Content) -> Option<&ClaimInfoOf<T>> {
+		self.claims.get(claim).filter(|info| !self.has_expired(info))
+	}
+
+	/// Get the account (if any) which a claim has been offered to.
+	pub fn pending_transfer(&self, claim: &T::Content) -> Option<&T::AccountId> {
+		self.pending_transfers.get(claim)
+	}
+
+	/// Get the number of claims owned by `owner`.
+	pub fn claim_count(&self, owner: &T::AccountId) -> usize {
+		self.claims_by_owner.get(owner).map(|claims| claims.len()).unwrap_or(0)
+	}
+
+	/// Get up to `limit` of the claims owned by `owner`, in order, skipping the first `start`.
+	/// A page which starts after the last claim is empty.
+	pub fn claims_of(&self, owner: &T::AccountId, start: usize, limit: usize) -> Vec<&T::Content> {
+		self.claims_by_owner
+			.get(owner)
+			.map(|claims| claims.iter().skip(start).take(limit).collect())
+			.unwrap_or_default()
+	}
+
+	/// Check whether `data` has been claimed, by hashing it with `Hashing`.
+	/// Returns the information about the claim, if it exists.
+	pub fn verify(&self, data: &[u8]) -> Option<&ClaimInfoOf<T>> {
+		self.get_claim(&T::Hashing::hash(data))
+	}
+
+	// Check whether a claim has expired, whether or not it has been removed yet.
+	fn has_expired(&self, info: &ClaimInfoOf<T>) -> bool {
+		info.expires_at.is_some_and(|expires_at| expires_at <= self.now)
+	}
+
+	// Remove an expired claim, release its deposit, and emit an event.
+	fn remove_expired(runtime: &mut T, claim: T::Content)
+	where
+		T: GetPallet<Self> + GetPallet<T::Currency> + GetPallet<crate::system::Pallet<T>>,
+	{
+		let poe = GetPallet::<Self>::pallet_mut(runtime);
+		let Some(info) = poe.claims.remove(&claim) else { return };
+		if let Some(expires_at) = info.expires_at {
+			poe.expiries.remove(&(expires_at, claim.clone()));
+		}
+		poe.pending_transfers.remove(&claim);
+		poe.remove_from_owner(&info.owner, &claim);
+		// The deposit was reserved by this pallet, so all of it can be unreserved.
+		let _ = T::Currency::unreserve(runtime, &info.owner, info.deposit);
+		GetPallet::<crate::system::Pallet<T>>::pallet_mut(runtime)
+			.deposit_event(Event::ClaimExpired { claim, owner: info.owner });
+	}
+
+	// Check that `who` can own another claim.
+	fn ensure_can_own_more(&self, who: &T::AccountId) -> DispatchResult {
+		if self.claim_count(who) >= T::MaxClaimsPerAccount::get() as usize {
+			return Err(Error::<T>::TooManyClaims.into())
+		}
+		Ok(())
 	}
 
-	/// Get the owner (if any) of a claim.
-	pub fn get_claim(&self, claim: &T::Content) -> Option<&T::AccountId> {
-		self.claims.get(claim)
+	// Record that `owner` owns `claim`.
+	fn add_to_owner(&mut self, owner: &T::AccountId, claim: &T::Content) {
+		self.claims_by_owner.entry(owner.clone()).or_default().insert(claim.clone());
 	}
+
+	// Record that `owner` no longer owns `claim`.
+	fn remove_from_owner(&mut self, owner: &T::AccountId, claim: &T::Content) {
+		if let Some(claims) = self.claims_by_owner.get_mut(owner) {
+			claims.remove(claim);
+			if claims.is_empty() {
+				self.claims_by_owner.remove(owner);
+			}
+		}
+	}
+}
+
+// At the start of each block, the proof of existence module removes up to `MaxExpiriesPerBlock`
+// expired claims, and releases their deposits. It also checks that the indices of claims agree
+// with the claims.
+impl<T> crate::support::Hooks<T, T::BlockNumber> for Pallet<T>
+where
+	T: Config + GetPallet<Self> + GetPallet<T::Currency> + GetPallet<crate::system::Pallet<T>>,
+{
+	fn on_initialize(runtime: &mut T, block_number: T::BlockNumber) {
+		GetPallet::<Self>::pallet_mut(runtime).now = block_number;
+		for _ in 0..T::MaxExpiriesPerBlock::get() {
+			let poe = GetPallet::<Self>::pallet_mut(runtime);
+			let Some((expires_at, _)) = poe.expiries.first() else { break };
+			if *expires_at > block_number {
+				break;
+			}
+			let (_, claim) = poe.expiries.pop_first().expect("we just checked the first expiry");
+			Self::remove_expired(runtime, claim);
+		}
+	}
+
+	fn try_state(&self, _block_number: T::BlockNumber) -> Result<(), &'static str> {
+		let indexed: usize = self.claims_by_owner.values().map(|claims| claims.len()).sum();
+		if indexed != self.claims.len() {
+			return Err("proof_of_existence: the number of indexed claims does not match")
+		}
+		for (owner, claims) in &self.claims_by_owner {
+			for claim in claims {
+				if self.claims.get(claim).map(|info| &info.owner) != Some(owner) {
+					return Err("proof_of_existence: a claim is indexed under the wrong owner")
+				}
+			}
+		}
+		for (expires_at, claim) in &self.expiries {
+			if self.claims.get(claim).and_then(|info| info.expires_at) != Some(*expires_at) {
+				return Err("proof_of_existence: an expiry does not match its claim")
+			}
+		}
+		if self.claims.values().filter(|info| info.expires_at.is_some()).count() !=
+			self.expiries.len()
+		{
+			return Err("proof_of_existence: the number of expiring claims does not match")
+		}
+		Ok(())
+	}
+}
+
+/// The errors which can be returned by the Proof of Existence Module.
+#[macros::error]
+pub enum Error<T> {
+	/// This content is already claimed.
+	AlreadyClaimed,
+	/// This claim does not exist.
+	NoSuchClaim,
+	/// This content is owned by someone else.
+	NotClaimOwner,
+	/// This claim has not been offered to the caller.
+	NoPendingTransfer,
+	/// The account already owns the maximum number of claims.
+	TooManyClaims,
+	/// This claim never expires, so it cannot be renewed.
+	NeverExpires,
+	/// The owner of the claim no longer has all of its deposit reserved, so it cannot move with
+	/// the claim.
+	DepositNotReserved,
 }
 
 #[macros::call]
 impl<T: Config> Pallet<T> {
 	/// Create a new claim on behalf of the `caller`.
-	/// This function will return an error if someone already has claimed that content.
-	pub fn create_claim(&mut self, caller: T::AccountId, claim: T::Content) -> DispatchResult {
-		if self.claims.contains_key(&claim) {
-			return Err("this content is already claimed");
+	/// The `ClaimDeposit` is reserved from the caller until the claim is revoked or expires.
+	/// This function will return an error if someone already has claimed that content. A claim
+	/// which has expired, but has not been removed yet, is removed first.
+	#[call_index(0)]
+	pub fn create_claim(runtime: &mut T, caller: T::AccountId, claim: T::Content) -> DispatchResult
+	where
+		T: GetPallet<Self> + GetPallet<T::Currency> + GetPallet<crate::system::Pallet<T>>,
+	{
+		let poe = GetPallet::<Self>::pallet(runtime);
+		if poe.get_claim(&claim).is_some() {
+			return Err(Error::<T>::AlreadyClaimed.into())
+		}
+		Self::remove_expired(runtime, claim.clone());
+		let poe = GetPallet::<Self>::pallet(runtime);
+		poe.ensure_can_own_more(&caller)?;
+
+		let deposit = T::ClaimDeposit::get();
+		GetPallet::<T::Currency>::pallet_mut(runtime).reserve(&caller, deposit)?;
+		let block_number = GetPallet::<crate::system::Pallet<T>>::pallet(runtime).block_number();
+		let poe = GetPallet::<Self>::pallet_mut(runtime);
+		poe.add_to_owner(&caller, &claim);
+		let expires_at = T::ClaimDuration::get().map(|duration| {
+			let mut expires_at = block_number;
+			expires_at += duration;
+			expires_at
+		});
+		if let Some(expires_at) = expires_at {
+			poe.expiries.insert((expires_at, claim.clone()));
 		}
-		self.claims.insert(claim, caller);
+		let info = ClaimInfo { owner: caller, block_number, deposit, expires_at };
+		poe.claims.insert(claim, info);
 		Ok(())
 	}
 
-	/// Revoke an existing claim on some content.
+	/// Revoke an existing claim on some content, and release its deposit.
 	/// This function should only succeed if the caller is the owner of an existing claim.
 	/// It will return an error if the claim does not exist, or if the caller is not the owner.
-	pub fn revoke_claim(&mut self, caller: T::AccountId, claim: T::Content) -> DispatchResult {
-		let owner = self.get_claim(&claim).ok_or("claim does not exist")?;
-		if caller != *owner {
-			return Err("this content is owned by someone else");
+	#[call_index(1)]
+	pub fn revoke_claim(runtime: &mut T, caller: T::AccountId, claim: T::Content) -> DispatchResult
+	where
+		T: GetPallet<Self> + GetPallet<T::Currency>,
+	{
+		let poe = GetPallet::<Self>::pallet_mut(runtime);
+		poe.ensure_owner(&caller, &claim)?;
+		let info = poe.claims.remove(&claim).ok_or(Error::<T>::NoSuchClaim)?;
+		poe.pending_transfers.remove(&claim);
+		poe.remove_from_owner(&caller, &claim);
+		if let Some(expires_at) = info.expires_at {
+			poe.expiries.remove(&(expires_at, claim));
+		}
+		// The deposit was reserved by this pallet, so all of it can be unreserved.
+		let _ = T::Currency::unreserve(runtime, &caller, info.deposit);
+		Ok(())
+	}
+
+	/// Offer a claim owned by the caller to `new_owner`, replacing any previous offer.
+	/// The claim only moves once `new_owner` accepts it with `accept_claim`, so nobody can take
+	/// it in between, and nobody is given a claim they did not want.
+	#[call_index(2)]
+	pub fn transfer_claim(
+		&mut self,
+		caller: T::AccountId,
+		claim: T::Content,
+		new_owner: T::AccountId,
+	) -> DispatchResult {
+		self.ensure_owner(&caller, &claim)?;
+		self.pending_transfers.insert(claim, new_owner);
+		Ok(())
+	}
+
+	/// Accept a claim which has been offered to the caller with `transfer_claim`.
+	/// The deposit moves with the claim, from the reserved balance of the previous owner to the
+	/// reserved balance of the caller. The block number of the claim is kept, since the content
+	/// has existed since then.
+	#[call_index(3)]
+	pub fn accept_claim(runtime: &mut T, caller: T::AccountId, claim: T::Content) -> DispatchResult
+	where
+		T: GetPallet<Self> + GetPallet<T::Currency>,
+	{
+		let poe = GetPallet::<Self>::pallet(runtime);
+		if poe.pending_transfer(&claim) != Some(&caller) {
+			return Err(Error::<T>::NoPendingTransfer.into())
+		}
+		poe.ensure_can_own_more(&caller)?;
+		let info = poe.get_claim(&claim).ok_or(Error::<T>::NoSuchClaim)?;
+		let (owner, deposit) = (info.owner.clone(), info.deposit);
+
+		if GetPallet::<T::Currency>::pallet(runtime).reserved_balance(&owner) < deposit {
+			return Err(Error::<T>::DepositNotReserved.into())
+		}
+		let remaining = T::Currency::repatriate_reserved(
+			runtime,
+			&owner,
+			&caller,
+			deposit,
+			BalanceStatus::Reserved,
+		)?;
+		if !remaining.is_zero() {
+			return Err(Error::<T>::DepositNotReserved.into())
+		}
+		let poe = GetPallet::<Self>::pallet_mut(runtime);
+		poe.pending_transfers.remove(&claim);
+		poe.remove_from_owner(&owner, &claim);
+		poe.add_to_owner(&caller, &claim);
+		if let Some(info) = poe.claims.get_mut(&claim) {
+			info.owner = caller;
+		}
+		Ok(())
+	}
+
+	/// Withdraw the offer of a claim owned by the caller, if there is one.
+	#[call_index(4)]
+	pub fn cancel_claim_transfer(
+		&mut self,
+		caller: T::AccountId,
+		claim: T::Content,
+	) -> DispatchResult {
+		self.ensure_owner(&caller, &claim)?;
+		self.pending_transfers.remove(&claim);
+		Ok(())
+	}
+
+	/// Create a new claim on behalf of the `caller`, on the hash of `data`.
+	/// This works just like `create_claim`, but only the hash of the data is stored, and anyone
+	/// with the data can check the claim with `verify`.
+	#[call_index(5)]
+	pub fn create_claim_from_bytes(
+		runtime: &mut T,
+		caller: T::AccountId,
+		data: Bytes,
+	) -> DispatchResult
+	where
+		T: GetPallet<Self> + GetPallet<T::Currency> + GetPallet<crate::system::Pallet<T>>,
+	{
+		Self::create_claim(runtime, caller, T::Hashing::hash(&data.0))
+	}
+
+	/// Renew a claim owned by the caller, so it lasts another `ClaimDuration` blocks.
+	/// The `RenewalFee` is burned from the free balance of the caller. A claim which has already
+	/// expired cannot be renewed, even if it has not been removed yet.
+	#[call_index(6)]
+	pub fn renew_claim(runtime: &mut T, caller: T::AccountId, claim: T::Content) -> DispatchResult
+	where
+		T: GetPallet<Self> + GetPallet<T::Currency>,
+	{
+		let poe = GetPallet::<Self>::pallet(runtime);
+		poe.ensure_owner(&caller, &claim)?;
+		let info = poe.get_claim(&claim).ok_or(Error::<T>::NoSuchClaim)?;
+		let old_expiry = info.expires_at.ok_or(Error::<T>::NeverExpires)?;
+		let duration = T::ClaimDuration::get().ok_or(Error::<T>::NeverExpires)?;
+
+		// The fee is not resolved into any account, so it is destroyed.
+		let currency = GetPallet::<T::Currency>::pallet_mut(runtime);
+		let _fee = currency.withdraw(&caller, T::RenewalFee::get())?;
+
+		let mut expires_at = old_expiry;
+		expires_at += duration;
+		let poe = GetPallet::<Self>::pallet_mut(runtime);
+		poe.expiries.remove(&(old_expiry, claim.clone()));
+		poe.expiries.insert((expires_at, claim.clone()));
+		if let Some(info) = poe.claims.get_mut(&claim) {
+			info.expires_at = Some(expires_at);
+		}
+		Ok(())
+	}
+
+	/// Check that `who` is the owner of an existing claim.
+	/// This is a helper function, and is not callable.
+	#[call(skip)]
+	fn ensure_owner(&self, who: &T::AccountId, claim: &T::Content) -> DispatchResult {
+		let info = self.get_claim(claim).ok_or(Error::<T>::NoSuchClaim)?;
+		if *who != info.owner {
+			return Err(Error::<T>::NotClaimOwner.into())
 		}
-		self.claims.remove(&claim);
 		Ok(())
 	}
 }
 
 #[cfg(test)]
 mod test {
-	struct TestConfig;
+	use super::{ClaimInfo, Error};
+	use crate::{
+		mock::{execute_next_block, new_test_runtime, RuntimeEvent, TestRuntime},
+		support::{Hash, Hooks, ReservableCurrency, Sha256, H256},
+	};
 
-	impl super::Config for TestConfig {
-		type Content = String;
-	}
+	type Poe = super::Pallet<TestRuntime>;
 
-	impl crate::system::Config for TestConfig {
-		type AccountId = String;
-		type BlockNumber = u32;
-		type Nonce = u32;
+	// A runtime where alice and bob can each afford two claims.
+	fn new_runtime() -> TestRuntime {
+		let mut runtime = new_test_runtime();
+		runtime.balances.set_balance(&"alice".to_string(), 10);
+		runtime.balances.set_balance(&"bob".to_string(), 10);
+		runtime
 	}
 
 	#[test]
 	fn basic_proof_of_existence() {
-		let mut poe = super::Pallet::<TestConfig>::new();
-		assert_eq!(poe.get_claim(&"Hello, world!".to_string()), None);
-		assert_eq!(poe.create_claim("alice".to_string(), "Hello, world!".to_string()), Ok(()));
-		assert_eq!(poe.get_claim(&"Hello, world!".to_string()), Some(&"alice".to_string()));
+		let mut runtime = new_runtime();
+		let content = "Hello, world!".to_string();
+		assert_eq!(runtime.proof_of_existence.get_claim(&content), None);
+		assert_eq!(Poe::create_claim(&mut runtime, "alice".to_string(), content.clone()), Ok(()));
+		assert_eq!(
+			runtime.proof_of_existence.get_claim(&content),
+			Some(&ClaimInfo {
+				owner: "alice".to_string(),
+				block_number: 0,
+				deposit: 5,
+				expires_at: Some(5)
+			})
+		);
+		assert_eq!(
+			Poe::create_claim(&mut runtime, "bob".to_string(), content.clone()),
+			Err(Error::<TestRuntime>::AlreadyClaimed.into())
+		);
+		assert_eq!(Poe::revoke_claim(&mut runtime, "alice".to_string(), content.clone()), Ok(()));
+
+		runtime.system.inc_block_number();
+		assert_eq!(Poe::create_claim(&mut runtime, "bob".to_string(), content.clone()), Ok(()));
+		assert_eq!(
+			runtime.proof_of_existence.get_claim(&content).map(|info| info.block_number),
+			Some(1)
+		);
+	}
+
+	#[test]
+	fn claims_reserve_a_deposit() {
+		let mut runtime = new_runtime();
+		let alice = "alice".to_string();
+
+		assert_eq!(Poe::create_claim(&mut runtime, alice.clone(), "a".to_string()), Ok(()));
+		assert_eq!(Poe::create_claim(&mut runtime, alice.clone(), "b".to_string()), Ok(()));
+		assert_eq!(runtime.balances.balance(&alice), 0);
+		assert_eq!(runtime.balances.reserved_balance(&alice), 10);
+
+		// Alice cannot afford the deposit for a third claim.
+		assert_eq!(
+			Poe::create_claim(&mut runtime, alice.clone(), "c".to_string()),
+			Err(crate::balances::Error::<TestRuntime>::InsufficientBalance.into())
+		);
+		assert_eq!(runtime.proof_of_existence.get_claim(&"c".to_string()), None);
+
+		assert_eq!(Poe::revoke_claim(&mut runtime, alice.clone(), "a".to_string()), Ok(()));
+		assert_eq!(runtime.balances.balance(&alice), 5);
+		assert_eq!(runtime.balances.reserved_balance(&alice), 5);
+	}
+
+	#[test]
+	fn revoke_claim_errors() {
+		let mut runtime = new_runtime();
+		let content = "Hello, world!".to_string();
+		assert_eq!(
+			Poe::revoke_claim(&mut runtime, "alice".to_string(), content.clone()),
+			Err(Error::<TestRuntime>::NoSuchClaim.into())
+		);
+		assert_eq!(Poe::create_claim(&mut runtime, "alice".to_string(), content.clone()), Ok(()));
+		assert_eq!(
+			Poe::revoke_claim(&mut runtime, "bob".to_string(), content),
+			Err(Error::<TestRuntime>::NotClaimOwner.into())
+		);
+		assert_eq!(runtime.balances.reserved_balance(&"alice".to_string()), 5);
+	}
+
+	#[test]
+	fn transfer_claim() {
+		let mut runtime = new_runtime();
+		let (alice, bob, charlie) = ("alice".to_string(), "bob".to_string(), "charlie".to_string());
+		let content = "Hello, world!".to_string();
+		assert_eq!(Poe::create_claim(&mut runtime, alice.clone(), content.clone()), Ok(()));
+		runtime.system.inc_block_number();
+
+		assert_eq!(
+			runtime.proof_of_existence.transfer_claim(
+				bob.clone(),
+				content.clone(),
+				charlie.clone()
+			),
+			Err(Error::<TestRuntime>::NotClaimOwner.into())
+		);
+		assert_eq!(
+			runtime.proof_of_existence.transfer_claim(
+				alice.clone(),
+				content.clone(),
+				charlie.clone()
+			),
+			Ok(())
+		);
+		// A new offer replaces the previous one.
+		assert_eq!(
+			runtime
+				.proof_of_existence
+				.transfer_claim(alice.clone(), content.clone(), bob.clone()),
+			Ok(())
+		);
+		assert_eq!(runtime.proof_of_existence.pending_transfer(&content), Some(&bob));
+		assert_eq!(
+			Poe::accept_claim(&mut runtime, charlie, content.clone()),
+			Err(Error::<TestRuntime>::NoPendingTransfer.into())
+		);
+
+		// The claim keeps its block number, and the deposit moves with it.
+		assert_eq!(Poe::accept_claim(&mut runtime, bob.clone(), content.clone()), Ok(()));
+		assert_eq!(
+			runtime.proof_of_existence.get_claim(&content),
+			Some(&ClaimInfo {
+				owner: bob.clone(),
+				block_number: 0,
+				deposit: 5,
+				expires_at: Some(5)
+			})
+		);
+		assert_eq!(runtime.proof_of_existence.pending_transfer(&content), None);
+		assert_eq!(
+			(runtime.balances.balance(&alice), runtime.balances.reserved_balance(&alice)),
+			(5, 0)
+		);
+		assert_eq!(
+			(runtime.balances.balance(&bob), runtime.balances.reserved_balance(&bob)),
+			(10, 5)
+		);
+
+		// Only the new owner can revoke the claim, which releases the deposit to them.
+		assert_eq!(
+			Poe::revoke_claim(&mut runtime, alice, content.clone()),
+			Err(Error::<TestRuntime>::NotClaimOwner.into())
+		);
+		assert_eq!(Poe::revoke_claim(&mut runtime, bob.clone(), content), Ok(()));
+		assert_eq!(runtime.balances.balance(&bob), 15);
+	}
+
+	#[test]
+	fn cancel_and_revoke_remove_offers() {
+		let mut runtime = new_runtime();
+		let (alice, bob) = ("alice".to_string(), "bob".to_string());
+		let content = "Hello, world!".to_string();
+		assert_eq!(Poe::create_claim(&mut runtime, alice.clone(), content.clone()), Ok(()));
+
+		assert_eq!(
+			runtime
+				.proof_of_existence
+				.transfer_claim(alice.clone(), content.clone(), bob.clone()),
+			Ok(())
+		);
+		assert_eq!(
+			runtime.proof_of_existence.cancel_claim_transfer(alice.clone(), content.clone()),
+			Ok(())
+		);
+		assert_eq!(
+			Poe::accept_claim(&mut runtime, bob.clone(), content.clone()),
+			Err(Error::<TestRuntime>::NoPendingTransfer.into())
+		);
+
+		// An offer does not survive the claim being revoked and claimed again.
+		assert_eq!(
+			runtime
+				.proof_of_existence
+				.transfer_claim(alice.clone(), content.clone(), bob.clone()),
+			Ok(())
+		);
+		assert_eq!(Poe::revoke_claim(&mut runtime, alice.clone(), content.clone()), Ok(()));
+		assert_eq!(Poe::create_claim(&mut runtime, alice.clone(), content.clone()), Ok(()));
+		assert_eq!(
+			Poe::accept_claim(&mut runtime, bob, content.clone()),
+			Err(Error::<TestRuntime>::NoPendingTransfer.into())
+		);
+		assert_eq!(
+			runtime.proof_of_existence.get_claim(&content).map(|info| &info.owner),
+			Some(&alice)
+		);
+	}
+
+	#[test]
+	fn claims_by_owner() {
+		let mut runtime = new_runtime();
+		let (alice, bob) = ("alice".to_string(), "bob".to_string());
+		runtime.balances.set_balance(&alice, 100);
+		for content in ["d", "b", "c"].map(String::from) {
+			assert_eq!(Poe::create_claim(&mut runtime, alice.clone(), content), Ok(()));
+		}
+
+		// Alice can only own three claims, which are listed in order.
+		assert_eq!(
+			Poe::create_claim(&mut runtime, alice.clone(), "a".to_string()),
+			Err(Error::<TestRuntime>::TooManyClaims.into())
+		);
+		assert_eq!(runtime.proof_of_existence.claim_count(&alice), 3);
+		assert_eq!(runtime.proof_of_existence.claims_of(&alice, 0, 2), vec!["b", "c"]);
+		assert_eq!(runtime.proof_of_existence.claims_of(&alice, 2, 2), vec!["d"]);
+		assert!(runtime.proof_of_existence.claims_of(&alice, 3, 2).is_empty());
+		assert!(runtime.proof_of_existence.claims_of(&bob, 0, 2).is_empty());
+		assert_eq!(runtime.balances.reserved_balance(&alice), 15);
+
+		assert_eq!(Poe::revoke_claim(&mut runtime, alice.clone(), "c".to_string()), Ok(()));
+		assert_eq!(runtime.proof_of_existence.claims_of(&alice, 0, 10), vec!["b", "d"]);
+
+		assert_eq!(
+			runtime
+				.proof_of_existence
+				.transfer_claim(alice.clone(), "d".to_string(), bob.clone()),
+			Ok(())
+		);
+		assert_eq!(Poe::accept_claim(&mut runtime, bob.clone(), "d".to_string()), Ok(()));
+		assert_eq!(runtime.proof_of_existence.claims_of(&alice, 0, 10), vec!["b"]);
+		assert_eq!(runtime.proof_of_existence.claims_of(&bob, 0, 10), vec!["d"]);
+		assert_eq!(runtime.proof_of_existence.try_state(0), Ok(()));
+	}
+
+	#[test]
+	fn accept_claim_respects_max_claims() {
+		let mut runtime = new_runtime();
+		let (alice, bob) = ("alice".to_string(), "bob".to_string());
+		runtime.balances.set_balance(&bob, 100);
+		for content in ["a", "b", "c"].map(String::from) {
+			assert_eq!(Poe::create_claim(&mut runtime, bob.clone(), content), Ok(()));
+		}
+		assert_eq!(Poe::create_claim(&mut runtime, alice.clone(), "d".to_string()), Ok(()));
+
+		assert_eq!(
+			runtime
+				.proof_of_existence
+				.transfer_claim(alice.clone(), "d".to_string(), bob.clone()),
+			Ok(())
+		);
+		assert_eq!(
+			Poe::accept_claim(&mut runtime, bob.clone(), "d".to_string()),
+			Err(Error::<TestRuntime>::TooManyClaims.into())
+		);
+		// Nothing moved, so the offer can be accepted once bob has room.
+		assert_eq!(runtime.balances.reserved_balance(&alice), 5);
+		assert_eq!(Poe::revoke_claim(&mut runtime, bob.clone(), "a".to_string()), Ok(()));
+		assert_eq!(Poe::accept_claim(&mut runtime, bob.clone(), "d".to_string()), Ok(()));
+		assert_eq!(runtime.proof_of_existence.claim_count(&bob), 3);
+		assert_eq!(runtime.proof_of_existence.claim_count(&alice), 0);
+	}
+
+	#[test]
+	fn accept_claim_requires_the_whole_deposit() {
+		let mut runtime = new_runtime();
+		let (alice, bob) = ("alice".to_string(), "bob".to_string());
+		assert_eq!(Poe::create_claim(&mut runtime, alice.clone(), "a".to_string()), Ok(()));
+		assert_eq!(
+			runtime
+				.proof_of_existence
+				.transfer_claim(alice.clone(), "a".to_string(), bob.clone()),
+			Ok(())
+		);
+
+		// Part of the deposit was slashed, so it cannot all move to bob.
+		assert_eq!(
+			crate::balances::Pallet::<TestRuntime>::slash_reserved(&mut runtime, &alice, 2),
+			0
+		);
+		assert_eq!(
+			Poe::accept_claim(&mut runtime, bob.clone(), "a".to_string()),
+			Err(Error::<TestRuntime>::DepositNotReserved.into())
+		);
+		assert_eq!(runtime.balances.reserved_balance(&alice), 3);
+		assert_eq!(runtime.balances.reserved_balance(&bob), 0);
+		assert_eq!(
+			runtime.proof_of_existence.get_claim(&"a".to_string()).map(|info| &info.owner),
+			Some(&alice)
+		);
+	}
+
+	#[test]
+	fn claims_from_bytes() {
+		let mut runtime = new_runtime();
+		let alice = "alice".to_string();
+		assert_eq!(runtime.proof_of_existence.verify(b"Hello, world!"), None);
+
+		assert_eq!(
+			Poe::create_claim_from_bytes(&mut runtime, alice.clone(), "Hello, world!".into()),
+			Ok(())
+		);
+		assert_eq!(
+			runtime.proof_of_existence.verify(b"Hello, world!").map(|info| &info.owner),
+			Some(&alice)
+		);
+		assert_eq!(runtime.proof_of_existence.verify(b"Hello, world"), None);
+		assert_eq!(
+			Poe::create_claim(&mut runtime, alice, "Hello, world!".to_string()),
+			Err(Error::<TestRuntime>::AlreadyClaimed.into())
+		);
+
+		let call = "create_claim_from_bytes data=0x6869".parse::<super::Call<TestRuntime>>();
+		assert_eq!(call, Ok(super::Call::create_claim_from_bytes { data: "hi".into() }));
+	}
+
+	#[test]
+	fn sha256() {
+		use crate::support::{Decode, Encode};
+
+		let digest = |hex: &str| hex.parse::<H256>().unwrap();
+		assert_eq!(
+			Sha256::hash(b""),
+			digest("0xe3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855")
+		);
+		assert_eq!(
+			Sha256::hash(b"abc"),
+			digest("0xba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+		);
+		// Long data is hashed in many blocks.
+		assert_eq!(
+			Sha256::hash(&[b'a'; 1000]),
+			digest("0x41edece42d63e8d9bf515a9ba6932e1c20cbc9f5a5d134645adb5db1b9737ea3")
+		);
+
+		let hello = Sha256::hash(b"Hello, world!");
+		assert_eq!(
+			format!("{hello:?}"),
+			"0x315f5bdb76d078c43b8ac0064e4a0164612b1fce77c869345bfc94c75894edd3"
+		);
+		assert_eq!(hello.encode().len(), 32);
+		assert_eq!(H256::decode(&mut &hello.encode()[..]), Ok(hello));
+		assert!("0x1234".parse::<H256>().is_err());
+	}
+
+	#[test]
+	fn claims_expire() {
+		let mut runtime = new_runtime();
+		let alice = "alice".to_string();
+		runtime.balances.set_balance(&alice, 100);
+		for content in ["a", "b", "c"].map(String::from) {
+			assert_eq!(Poe::create_claim(&mut runtime, alice.clone(), content), Ok(()));
+		}
+		runtime.system.inc_block_number();
+		assert_eq!(Poe::revoke_claim(&mut runtime, alice.clone(), "c".to_string()), Ok(()));
+
+		// Nothing expires before the expiry block.
+		Poe::on_initialize(&mut runtime, 4);
+		assert!(runtime.system.events().is_empty());
+		assert_eq!(runtime.proof_of_existence.claim_count(&alice), 2);
+
+		Poe::on_initialize(&mut runtime, 5);
+		assert!(matches!(
+			runtime.system.events(),
+			[RuntimeEvent::ProofOfExistence(super::Event::ClaimExpired { claim: a, owner: o1 }),
+				RuntimeEvent::ProofOfExistence(super::Event::ClaimExpired { claim: b, owner: o2 })]
+				if a == "a" && b == "b" && *o1 == alice && *o2 == alice
+		));
+		assert_eq!(runtime.proof_of_existence.get_claim(&"a".to_string()), None);
+		assert_eq!(runtime.proof_of_existence.claim_count(&alice), 0);
+		assert_eq!(runtime.proof_of_existence.try_state(5), Ok(()));
+
+		// The deposits of the expired claims are released to alice.
+		assert_eq!(runtime.balances.reserved_balance(&alice), 0);
+		assert_eq!(runtime.balances.balance(&alice), 100);
+		assert_eq!(runtime.balances.try_state(5), Ok(()));
+	}
+
+	#[test]
+	fn claims_expire_in_a_block() {
+		let mut runtime = new_runtime();
+		let alice = "alice".to_string();
+		assert_eq!(Poe::create_claim(&mut runtime, alice.clone(), "a".to_string()), Ok(()));
+
+		for _ in 0..4 {
+			assert_eq!(execute_next_block(&mut runtime, vec![]), Ok(()));
+		}
+		assert_eq!(runtime.balances.reserved_balance(&alice), 5);
+
+		// The claim expires at the start of block 5, and the event is kept for that block.
+		assert_eq!(execute_next_block(&mut runtime, vec![]), Ok(()));
+		assert_eq!(runtime.proof_of_existence.get_claim(&"a".to_string()), None);
+		assert_eq!(runtime.balances.reserved_balance(&alice), 0);
+		assert_eq!(runtime.balances.balance(&alice), 10);
+		assert!(matches!(
+			runtime.system.events(),
+			[RuntimeEvent::ProofOfExistence(super::Event::ClaimExpired { claim, owner })]
+				if claim == "a" && *owner == alice
+		));
+
+		// Events only last for one block.
+		assert_eq!(execute_next_block(&mut runtime, vec![]), Ok(()));
+		assert!(runtime.system.events().is_empty());
+	}
+
+	#[test]
+	fn expiry_is_bounded_per_block() {
+		let mut runtime = new_runtime();
+		let (alice, bob) = ("alice".to_string(), "bob".to_string());
+		runtime.balances.set_balance(&alice, 100);
+		runtime.balances.set_balance(&bob, 100);
+		for content in ["a", "b", "c"].map(String::from) {
+			assert_eq!(Poe::create_claim(&mut runtime, alice.clone(), content), Ok(()));
+		}
+		assert_eq!(Poe::create_claim(&mut runtime, bob.clone(), "d".to_string()), Ok(()));
+
+		// Only two claims are removed in each block, and the rest are removed later.
+		Poe::on_initialize(&mut runtime, 5);
+		assert_eq!(runtime.system.events().len(), 2);
+		assert_eq!(runtime.proof_of_existence.claims_of(&alice, 0, 10), vec!["c"]);
+		assert_eq!(runtime.balances.reserved_balance(&alice), 5);
+		Poe::on_initialize(&mut runtime, 6);
+		assert_eq!(runtime.system.events().len(), 4);
+		assert_eq!(runtime.proof_of_existence.claim_count(&alice), 0);
+		assert_eq!(runtime.proof_of_existence.claim_count(&bob), 0);
+		assert_eq!(runtime.proof_of_existence.try_state(6), Ok(()));
+	}
+
+	#[test]
+	fn renew_claim() {
+		let mut runtime = new_runtime();
+		let (alice, bob) = ("alice".to_string(), "bob".to_string());
+		let content = "Hello, world!".to_string();
+		assert_eq!(Poe::create_claim(&mut runtime, alice.clone(), content.clone()), Ok(()));
+
+		assert_eq!(
+			Poe::renew_claim(&mut runtime, bob, content.clone()),
+			Err(Error::<TestRuntime>::NotClaimOwner.into())
+		);
+		// The fee is burned, and the claim lasts another `ClaimDuration` blocks.
+		assert_eq!(Poe::renew_claim(&mut runtime, alice.clone(), content.clone()), Ok(()));
+		assert_eq!(runtime.balances.balance(&alice), 4);
+		assert_eq!(runtime.balances.total_issuance(), 19);
+		assert_eq!(
+			runtime.proof_of_existence.get_claim(&content).and_then(|info| info.expires_at),
+			Some(10)
+		);
+
+		Poe::on_initialize(&mut runtime, 5);
+		assert!(runtime.system.events().is_empty());
+		assert_eq!(runtime.proof_of_existence.try_state(5), Ok(()));
+
+		// The claim is not renewed if alice cannot pay the fee.
+		runtime.balances.set_balance(&alice, 0);
+		assert_eq!(
+			Poe::renew_claim(&mut runtime, alice, content.clone()),
+			Err(crate::balances::Error::<TestRuntime>::InsufficientBalance.into())
+		);
+		assert_eq!(
+			runtime.proof_of_existence.get_claim(&content).and_then(|info| info.expires_at),
+			Some(10)
+		);
+	}
+
+	#[test]
+	fn claims_which_are_not_removed_yet_still_expire() {
+		let mut runtime = new_runtime();
+		let (alice, bob) = ("alice".to_string(), "bob".to_string());
+		runtime.balances.set_balance(&alice, 100);
+		runtime.balances.set_balance(&bob, 100);
+		for content in ["a", "b", "c"].map(String::from) {
+			assert_eq!(Poe::create_claim(&mut runtime, alice.clone(), content), Ok(()));
+		}
+		assert_eq!(Poe::create_claim_from_bytes(&mut runtime, bob.clone(), "d".into()), Ok(()));
+		let d = "d".to_string();
+		assert_eq!(
+			runtime
+				.proof_of_existence
+				.transfer_claim(alice.clone(), "c".to_string(), bob.clone()),
+			Ok(())
+		);
+
+		// Only two of the four claims are removed at block 5, but none of them are live.
+		for _ in 0..5 {
+			runtime.system.inc_block_number();
+		}
+		Poe::on_initialize(&mut runtime, 5);
+		assert_eq!(runtime.system.events().len(), 2);
+		assert_eq!(runtime.proof_of_existence.claim_count(&alice), 1);
+		assert_eq!(runtime.proof_of_existence.get_claim(&"c".to_string()), None);
+		assert_eq!(runtime.proof_of_existence.get_claim(&d), None);
+		assert_eq!(runtime.proof_of_existence.verify(b"d"), None);
+		assert_eq!(
+			Poe::accept_claim(&mut runtime, bob.clone(), "c".to_string()),
+			Err(Error::<TestRuntime>::NoSuchClaim.into())
+		);
+		assert_eq!(
+			Poe::renew_claim(&mut runtime, alice.clone(), "c".to_string()),
+			Err(Error::<TestRuntime>::NoSuchClaim.into())
+		);
+		assert_eq!(runtime.balances.reserved_balance(&alice), 5);
+
+		// The content can be claimed again, which removes the old claim and releases its deposit.
+		assert_eq!(Poe::create_claim(&mut runtime, alice.clone(), d.clone()), Ok(()));
+		assert_eq!(runtime.balances.reserved_balance(&bob), 0);
+		assert_eq!(runtime.proof_of_existence.get_claim(&d).map(|info| &info.owner), Some(&alice));
+		assert_eq!(runtime.system.events().len(), 3);
+		assert_eq!(runtime.proof_of_existence.try_state(5), Ok(()));
+
+		Poe::on_initialize(&mut runtime, 6);
+		assert_eq!(runtime.proof_of_existence.claims_of(&alice, 0, 10), vec![&d]);
+		assert_eq!(runtime.proof_of_existence.try_state(6), Ok(()));
+	}
+
+	#[test]
+	fn error_messages_come_from_docs() {
+		let error = Error::<TestRuntime>::NotClaimOwner;
+		assert_eq!(error.index(), 2);
+		assert_eq!(error.to_string(), "This content is owned by someone else.");
+
+		let dispatch_error: crate::support::DispatchError = error.into();
 		assert_eq!(
-			poe.create_claim("bob".to_string(), "Hello, world!".to_string()),
-			Err("this content is already claimed")
+			dispatch_error,
+			crate::support::DispatchError::Module(crate::support::ModuleError {
+				index: 2,
+				error: 2,
+				message: "This content is owned by someone else.",
+			})
 		);
-		assert_eq!(poe.revoke_claim("alice".to_string(), "Hello, world!".to_string()), Ok(()));
-		assert_eq!(poe.create_claim("bob".to_string(), "Hello, world!".to_string()), Ok(()));
 	}
 }
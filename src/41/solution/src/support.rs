@@ -25,8 +25,107 @@ pub struct Extrinsic<Caller, Call> {
 }
 
 /// The Result type for our runtime. When everything completes successfully, we return `Ok(())`,
-/// otherwise return a static error message.
-pub type DispatchResult = Result<(), &'static str>;
+/// otherwise return a `DispatchError`.
+pub type DispatchResult = Result<(), DispatchError>;
+
+/// The error returned when dispatching a call fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DispatchError {
+	/// Some error with a static error message.
+	Other(&'static str),
+	/// An error coming from the `Error` enum of a pallet.
+	Module(ModuleError),
+	/// The call is not allowed by the `BaseCallFilter` of the runtime.
+	CallFiltered,
+	/// The caller is not allowed to make this call, for example because only root can make it.
+	BadOrigin,
+}
+
+/// The information about an error coming from a pallet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModuleError {
+	/// The index of the pallet in the runtime.
+	pub index: u8,
+	/// The index of the error in the `Error` enum of the pallet.
+	pub error: u8,
+	/// The error message, taken from the docs of the error.
+	pub message: &'static str,
+}
+
+impl From<&'static str> for DispatchError {
+	fn from(message: &'static str) -> Self {
+		Self::Other(message)
+	}
+}
+
+impl core::fmt::Display for DispatchError {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			Self::Other(message) => f.write_str(message),
+			Self::Module(ModuleError { index, error, message }) =>
+				write!(f, "{message} (pallet: {index}, error: {error})"),
+			Self::CallFiltered => f.write_str("call is not allowed by the call filter"),
+			Self::BadOrigin => f.write_str("caller is not allowed to make this call"),
+		}
+	}
+}
+
+/// A trait which allows a pallet to find information about itself in the runtime.
+///
+/// This is implemented for the `Runtime` by our `runtime` macro.
+pub trait PalletInfo {
+	/// The index of the pallet `P` in the runtime, if it is included.
+	fn index<P: 'static>() -> Option<u8>;
+	/// The name of the pallet `P` in the runtime, if it is included.
+	fn name<P: 'static>() -> Option<&'static str>;
+}
+
+/// Pallets which are tested on their own are not part of a runtime, so they have no index or name.
+impl PalletInfo for () {
+	fn index<P: 'static>() -> Option<u8> {
+		None
+	}
+
+	fn name<P: 'static>() -> Option<&'static str> {
+		None
+	}
+}
+
+/// A marker for an extra instance of an instanced pallet, like `Pallet<T, Instance1>`.
+///
+/// An instanced pallet can be included in the runtime more than once, with each instance having its
+/// own storage and configuration. The default instance of a pallet is simply `()`. This type is
+/// only used as a marker, so it can never be constructed.
+#[derive(Debug)]
+pub enum Instance1 {}
+
+/// The metadata of a runtime, describing all of the pallets it includes.
+#[derive(Debug)]
+pub struct RuntimeMetadata {
+	pub pallets: Vec<PalletMetadata>,
+}
+
+/// The metadata of a single pallet in the runtime.
+#[derive(Debug)]
+pub struct PalletMetadata {
+	/// The name of the pallet in the runtime.
+	pub name: &'static str,
+	/// The index of the pallet in the runtime.
+	pub index: u8,
+	/// The errors which can be returned by this pallet.
+	pub errors: Vec<ErrorMetadata>,
+}
+
+/// The metadata of a single pallet error.
+#[derive(Debug)]
+pub struct ErrorMetadata {
+	/// The name of the error variant.
+	pub name: &'static str,
+	/// The index of the error in the `Error` enum of the pallet.
+	pub index: u8,
+	/// The docs of the error, which are also used as the error message.
+	pub docs: &'static str,
+}
 
 /// A trait which allows us to dispatch an incoming extrinsic to the appropriate state transition
 /// function call.
@@ -40,3 +139,671 @@ pub trait Dispatch {
 	/// based on the outcome of that function call.
 	fn dispatch(&mut self, caller: Self::Caller, call: Self::Call) -> DispatchResult;
 }
+
+/// A trait which allows a pallet to dispatch its calls with access to the whole `Runtime`.
+///
+/// This is implemented for every pallet by our `call` macro, and used by our `runtime` macro to
+/// route each call to its pallet. Calls which take `&mut self` are given the pallet, found in the
+/// runtime with `GetPallet`. Calls which take `runtime: &mut T` are given the whole runtime, so
+/// they can also use other pallets.
+pub trait DispatchWithRuntime<Runtime> {
+	/// The type used to identify the caller of the function.
+	type Caller;
+	/// The state transition function call the caller is trying to access.
+	type Call;
+
+	/// This function takes a `caller` and the `call` they want to make, and returns a `Result`
+	/// based on the outcome of that function call.
+	fn dispatch_with_runtime(
+		runtime: &mut Runtime,
+		caller: Self::Caller,
+		call: Self::Call,
+	) -> DispatchResult;
+}
+
+/// A trait which allows us to look inside an "outer" call, like the `RuntimeCall`, and find the
+/// call of a single pallet.
+///
+/// This is implemented for the `RuntimeCall` by our `runtime` macro, for the `Call` of every
+/// pallet. It allows a pallet to inspect the calls of another pallet without knowing about the
+/// rest of the runtime.
+pub trait IsSubType<Call> {
+	/// Returns the pallet call, if this is a call to that pallet.
+	fn is_sub_type(&self) -> Option<&Call>;
+}
+
+/// A filter which decides which calls are allowed to be dispatched.
+///
+/// The runtime generated by our `runtime` macro checks every call against the `BaseCallFilter` of
+/// the system pallet before dispatching it. This allows us to disable whole pallets or specific
+/// calls, without removing them from the runtime. The filter is given access to the `Runtime`, so
+/// it can depend on the state of a pallet, like the paused calls of the `tx_pause` pallet.
+pub trait CallFilter<Runtime: ?Sized, Call: ?Sized> {
+	/// Returns `true` if the `call` is allowed to be dispatched.
+	fn allows(runtime: &Runtime, call: &Call) -> bool;
+}
+
+/// Every call is allowed when there is no filter.
+impl<Runtime: ?Sized, Call: ?Sized> CallFilter<Runtime, Call> for () {
+	fn allows(_runtime: &Runtime, _call: &Call) -> bool {
+		true
+	}
+}
+
+/// A trait which gives the name of the pallet and the function a call is for.
+///
+/// This is implemented for the `RuntimeCall` by our `runtime` macro. It allows a pallet to find out
+/// about any call, without knowing the calls of every other pallet.
+pub trait CallMetadata {
+	/// The name of the pallet the call is routed to.
+	fn pallet_name(&self) -> &'static str;
+	/// The name of the call within its pallet.
+	fn call_name(&self) -> &'static str;
+	/// The names of all the calls of the pallet named `pallet`, if the runtime includes it.
+	fn call_names(pallet: &str) -> Option<&'static [&'static str]>;
+}
+
+/// A trait which gives access to a pallet `P` included in the runtime.
+///
+/// This is implemented for the `Runtime` by our `runtime` macro, for every pallet it includes.
+pub trait GetPallet<P> {
+	/// Get the pallet.
+	fn pallet(&self) -> &P;
+	/// Get the pallet, so that it can be changed.
+	fn pallet_mut(&mut self) -> &mut P;
+}
+
+/// Functions which are called by the runtime at certain points during the execution of a block.
+///
+/// Every pallet included in the runtime, other than the system pallet, must implement this trait.
+/// Every function does nothing by default, so an empty `impl` is enough for most pallets.
+pub trait Hooks<Runtime: ?Sized, BlockNumber> {
+	/// Called at the start of every block, before any of its extrinsics are executed.
+	/// This is given the whole `Runtime`, so that a pallet can also use other pallets, like
+	/// releasing deposits held by the currency.
+	fn on_initialize(_runtime: &mut Runtime, _block_number: BlockNumber) {}
+
+	/// Check that the storage of the pallet is consistent, for example that the balances of all
+	/// accounts add up to the total issuance. This is used in tests, and can be run by the runtime
+	/// after every block.
+	fn try_state(&self, _block_number: BlockNumber) -> Result<(), &'static str> {
+		Ok(())
+	}
+}
+
+/// A hook which is called when an account is removed from storage, or "reaped", for example
+/// because its balance fell below the existential deposit.
+///
+/// This is given the whole `Runtime`, so that other pallets can clean up what they store for the
+/// account.
+pub trait OnKilledAccount<Runtime: ?Sized, AccountId> {
+	/// Called after the account `who` has been reaped.
+	fn on_killed_account(runtime: &mut Runtime, who: &AccountId);
+}
+
+/// Nothing needs to happen when an account is reaped.
+impl<Runtime: ?Sized, AccountId> OnKilledAccount<Runtime, AccountId> for () {
+	fn on_killed_account(_runtime: &mut Runtime, _who: &AccountId) {}
+}
+
+/// Funds which were taken out of an account by `Fungible::withdraw`.
+///
+/// The funds are destroyed as soon as they are withdrawn, so the total issuance is always right,
+/// even if this is dropped. To move the funds rather than destroy them, give this to
+/// `Fungible::resolve`, which puts them into another account and adds them back to the total
+/// issuance.
+#[must_use = "the withdrawn funds are destroyed unless they are resolved into an account"]
+#[derive(Debug, PartialEq, Eq)]
+pub struct NegativeImbalance<Balance>(Balance);
+
+impl<Balance: Copy> NegativeImbalance<Balance> {
+	/// Create a new imbalance of `amount`. Only a currency should do this.
+	pub fn new(amount: Balance) -> Self {
+		Self(amount)
+	}
+
+	/// The amount of this imbalance.
+	pub fn peek(&self) -> Balance {
+		self.0
+	}
+}
+
+/// A fungible token, where every unit is the same and can be moved between accounts.
+///
+/// This is implemented by the balances pallet and by each asset of the assets pallet, so other
+/// pallets can depend on this trait instead of a concrete pallet, and reach the token through
+/// `GetPallet`.
+///
+/// Funds moved through this trait never reap an account: an account is always left with at least
+/// the minimum balance, or nothing is moved.
+pub trait Fungible<AccountId> {
+	/// The type of the balance of an account.
+	type Balance: Copy;
+
+	/// The total amount of this token which exists.
+	fn total_issuance(&self) -> Self::Balance;
+
+	/// The minimum balance an account must have to exist.
+	fn minimum_balance(&self) -> Self::Balance;
+
+	/// Get the balance of `who` which is free to be used.
+	fn free_balance(&self, who: &AccountId) -> Self::Balance;
+
+	/// Move `amount` from the free balance of `from` to `to`.
+	fn transfer(
+		&mut self,
+		from: &AccountId,
+		to: &AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult;
+
+	/// Take `amount` out of the free balance of `who`, for example to pay a fee. The funds are
+	/// destroyed, reducing the total issuance, unless the returned imbalance is resolved.
+	fn withdraw(
+		&mut self,
+		who: &AccountId,
+		amount: Self::Balance,
+	) -> Result<NegativeImbalance<Self::Balance>, DispatchError>;
+
+	/// Create `amount` in the free balance of `who`, for example to pay a reward, increasing the
+	/// total issuance.
+	fn deposit(&mut self, who: &AccountId, amount: Self::Balance) -> DispatchResult;
+
+	/// Put the funds taken out of an account by `withdraw` into the free balance of `who`, adding
+	/// them back to the total issuance. If this fails, the funds stay destroyed.
+	fn resolve(
+		&mut self,
+		who: &AccountId,
+		imbalance: NegativeImbalance<Self::Balance>,
+	) -> DispatchResult {
+		self.deposit(who, imbalance.peek())
+	}
+}
+
+/// Where funds moved by `ReservableCurrency::repatriate_reserved` end up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BalanceStatus {
+	/// The funds are added to the free balance of the beneficiary.
+	Free,
+	/// The funds are added to the reserved balance of the beneficiary.
+	Reserved,
+}
+
+/// A currency where funds can be reserved, for example as a deposit for some data kept in storage.
+/// Reserved funds still belong to the account, but cannot be transferred until they are
+/// unreserved. An account with reserved funds is never reaped, but once they are gone, an account
+/// with less than the minimum balance is reaped.
+///
+/// The functions which can reap an account are given the whole `Runtime`, so that the currency can
+/// let other pallets clean up, like with `OnKilledAccount`.
+pub trait ReservableCurrency<Runtime: ?Sized, AccountId>: Fungible<AccountId> {
+	/// Get the reserved balance of an account `who`.
+	fn reserved_balance(&self, who: &AccountId) -> Self::Balance;
+
+	/// Whether `amount` of the free balance of `who` can be reserved.
+	fn can_reserve(&self, who: &AccountId, amount: Self::Balance) -> bool;
+
+	/// Move `amount` from the free balance of `who` to its reserved balance.
+	fn reserve(&mut self, who: &AccountId, amount: Self::Balance) -> DispatchResult;
+
+	/// Move up to `amount` from the reserved balance of `who` back to its free balance.
+	/// Returns the amount which could not be unreserved.
+	fn unreserve(runtime: &mut Runtime, who: &AccountId, amount: Self::Balance) -> Self::Balance;
+
+	/// Destroy up to `amount` of the reserved balance of `who`, reducing the total issuance.
+	/// Returns the amount which could not be slashed.
+	fn slash_reserved(
+		runtime: &mut Runtime,
+		who: &AccountId,
+		amount: Self::Balance,
+	) -> Self::Balance;
+
+	/// Move up to `amount` from the reserved balance of `slashed` to the free or reserved balance
+	/// of `beneficiary`, which must already exist. Returns the amount which could not be moved.
+	fn repatriate_reserved(
+		runtime: &mut Runtime,
+		slashed: &AccountId,
+		beneficiary: &AccountId,
+		amount: Self::Balance,
+		status: BalanceStatus,
+	) -> Result<Self::Balance, DispatchError>;
+}
+
+/// The name of a lock, like `*b"vesting "`, so that each pallet can manage its own locks.
+pub type LockIdentifier = [u8; 8];
+
+/// A currency where part of the balance of an account can be locked under a `LockIdentifier`.
+/// Locked funds still belong to the account, but the free balance cannot be transferred, reserved
+/// or burned below the largest lock. Locks overlap rather than add up, so the same funds can be
+/// locked by several pallets at once.
+pub trait LockableCurrency<AccountId>: Fungible<AccountId> {
+	/// Get the amount of the free balance of `who` which cannot be transferred, which is the
+	/// largest of its locks.
+	fn frozen_balance(&self, who: &AccountId) -> Self::Balance;
+
+	/// Lock `amount` of the free balance of `who` under `id`, replacing any lock with the same
+	/// `id`. Locking zero removes the lock.
+	fn set_lock(&mut self, id: LockIdentifier, who: &AccountId, amount: Self::Balance);
+
+	/// Lock `amount` of the free balance of `who` under `id`, or keep the existing lock with the
+	/// same `id` if it is larger.
+	fn extend_lock(&mut self, id: LockIdentifier, who: &AccountId, amount: Self::Balance);
+
+	/// Remove the lock of `who` with `id`, if there is one.
+	fn remove_lock(&mut self, id: LockIdentifier, who: &AccountId);
+}
+
+/// The native currency of the runtime, which can be moved, reserved and locked.
+///
+/// Pallets which charge or pay tokens take `type Currency: Currency<Self, Self::AccountId>` in
+/// their `Config`, instead of depending on the balances pallet directly. This is implemented for
+/// every type which implements all of the currency traits.
+pub trait Currency<Runtime: ?Sized, AccountId>:
+	ReservableCurrency<Runtime, AccountId> + LockableCurrency<AccountId>
+{
+}
+
+impl<Runtime: ?Sized, AccountId, C> Currency<Runtime, AccountId> for C where
+	C: ReservableCurrency<Runtime, AccountId> + LockableCurrency<AccountId>
+{
+}
+
+/// A trait which gives some value, usually a constant used to configure a pallet.
+///
+/// Types implementing this trait are usually created with the `parameter_types!` macro.
+pub trait Get<T> {
+	/// Get the value.
+	fn get() -> T;
+}
+
+/// Create types which implement `Get`, for configuring pallets.
+///
+/// For example, `parameter_types! { pub RootAccount: String = "root".to_string(); }` creates a
+/// type `RootAccount` which implements `Get<String>`.
+macro_rules! parameter_types {
+	( $( $( #[$attr:meta] )* $vis:vis $name:ident: $type:ty = $value:expr; )* ) => {
+		$(
+			$( #[$attr] )*
+			$vis struct $name;
+
+			impl $crate::support::Get<$type> for $name {
+				fn get() -> $type {
+					$value
+				}
+			}
+		)*
+	};
+}
+pub(crate) use parameter_types;
+
+/// The error returned when parsing a call from a string fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCallError {
+	/// The string is not formatted like `pallet.call name=value`.
+	InvalidFormat(&'static str),
+	/// There is no pallet with this name in the runtime.
+	UnknownPallet(String),
+	/// There is no call with this name in the pallet.
+	UnknownCall(String),
+	/// The call has no argument with this name.
+	UnknownArgument(String),
+	/// The argument with this name is given more than once.
+	DuplicateArgument(String),
+	/// The argument with this name is required, but was not given.
+	MissingArgument(&'static str),
+	/// The value given for an argument could not be parsed into the type of that argument.
+	InvalidArgument { name: &'static str, value: String },
+}
+
+impl core::fmt::Display for ParseCallError {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			Self::InvalidFormat(message) => f.write_str(message),
+			Self::UnknownPallet(pallet) => write!(f, "unknown pallet `{pallet}`"),
+			Self::UnknownCall(call) => write!(f, "unknown call `{call}`"),
+			Self::UnknownArgument(name) => write!(f, "unknown argument `{name}`"),
+			Self::DuplicateArgument(name) => write!(f, "argument `{name}` is given more than once"),
+			Self::MissingArgument(name) => write!(f, "missing argument `{name}`"),
+			Self::InvalidArgument { name, value } =>
+				write!(f, "invalid value `{value}` for argument `{name}`"),
+		}
+	}
+}
+
+/// The arguments of a call which is being parsed from a string, like `transfer to=bob amount=30`.
+///
+/// Values which contain spaces can be wrapped in double quotes, like `claim="Hello, world!"`. This
+/// is used by the `FromStr` implementation generated for each `Call` by our `call` macro.
+pub struct CallArgs {
+	/// The `(name, value)` of each argument which has not been taken yet.
+	args: Vec<(String, String)>,
+}
+
+impl CallArgs {
+	/// Split a string into the name of the call and its arguments.
+	pub fn parse(input: &str) -> Result<(String, Self), ParseCallError> {
+		let mut words = split_words(input)?.into_iter();
+		let call_name = words.next().ok_or(ParseCallError::InvalidFormat("missing call name"))?;
+
+		let mut args: Vec<(String, String)> = Vec::new();
+		for word in words {
+			let (name, value) = word
+				.split_once('=')
+				.ok_or(ParseCallError::InvalidFormat("expected arguments like `name=value`"))?;
+			if args.iter().any(|(other, _)| other == name) {
+				return Err(ParseCallError::DuplicateArgument(name.to_string()))
+			}
+			args.push((name.to_string(), value.to_string()));
+		}
+
+		Ok((call_name, Self { args }))
+	}
+
+	/// Take the argument called `name`, and parse its value.
+	pub fn take<V: core::str::FromStr>(&mut self, name: &'static str) -> Result<V, ParseCallError> {
+		let position = self
+			.args
+			.iter()
+			.position(|(other, _)| other == name)
+			.ok_or(ParseCallError::MissingArgument(name))?;
+		let (_, value) = self.args.remove(position);
+		value.parse().map_err(|_| ParseCallError::InvalidArgument { name, value })
+	}
+
+	/// Check that every argument has been taken, so that no argument is silently ignored.
+	pub fn finish(self) -> Result<(), ParseCallError> {
+		match self.args.into_iter().next() {
+			Some((name, _)) => Err(ParseCallError::UnknownArgument(name)),
+			None => Ok(()),
+		}
+	}
+}
+
+/// Split a string into words separated by whitespace. Whitespace inside double quotes is kept, and
+/// the quotes are removed.
+fn split_words(input: &str) -> Result<Vec<String>, ParseCallError> {
+	let mut words = Vec::new();
+	let mut word = String::new();
+	let mut in_word = false;
+	let mut in_quotes = false;
+	for c in input.chars() {
+		match c {
+			'"' => {
+				in_quotes = !in_quotes;
+				in_word = true;
+			},
+			c if c.is_whitespace() && !in_quotes =>
+				if in_word {
+					words.push(core::mem::take(&mut word));
+					in_word = false;
+				},
+			c => {
+				word.push(c);
+				in_word = true;
+			},
+		}
+	}
+	if in_quotes {
+		return Err(ParseCallError::InvalidFormat("missing closing quote"))
+	}
+	if in_word {
+		words.push(word);
+	}
+	Ok(words)
+}
+
+/// A very simple encoding format, used to turn things like calls into bytes, and back again.
+///
+/// On a real blockchain, you would use a proper codec like SCALE. Our encoding simply writes
+/// integers as little endian bytes, and prefixes strings and vectors with their length as a `u32`.
+pub trait Encode {
+	/// Append the encoded bytes of `self` to `dest`.
+	fn encode_to(&self, dest: &mut Vec<u8>);
+
+	/// Encode `self` into a new vector of bytes.
+	fn encode(&self) -> Vec<u8> {
+		let mut dest = Vec::new();
+		self.encode_to(&mut dest);
+		dest
+	}
+}
+
+/// The opposite of `Encode`: read some type from the front of a slice of bytes.
+pub trait Decode: Sized {
+	/// Decode `Self` from the front of `input`, moving `input` past the bytes we have read.
+	fn decode(input: &mut &[u8]) -> Result<Self, &'static str>;
+}
+
+/// Read exactly `len` bytes from the front of `input`.
+fn read_bytes<'a>(input: &mut &'a [u8], len: usize) -> Result<&'a [u8], &'static str> {
+	if input.len() < len {
+		return Err("not enough data to decode")
+	}
+	let (bytes, rest) = input.split_at(len);
+	*input = rest;
+	Ok(bytes)
+}
+
+macro_rules! impl_codec_for_int {
+	( $( $int:ty ),* ) => {
+		$(
+			impl Encode for $int {
+				fn encode_to(&self, dest: &mut Vec<u8>) {
+					dest.extend_from_slice(&self.to_le_bytes());
+				}
+			}
+
+			impl Decode for $int {
+				fn decode(input: &mut &[u8]) -> Result<Self, &'static str> {
+					let bytes = read_bytes(input, core::mem::size_of::<$int>())?;
+					Ok(<$int>::from_le_bytes(bytes.try_into().expect("we read the right length")))
+				}
+			}
+		)*
+	};
+}
+
+impl_codec_for_int!(u8, u16, u32, u64, u128);
+
+impl Encode for String {
+	fn encode_to(&self, dest: &mut Vec<u8>) {
+		(self.len() as u32).encode_to(dest);
+		dest.extend_from_slice(self.as_bytes());
+	}
+}
+
+impl Decode for String {
+	fn decode(input: &mut &[u8]) -> Result<Self, &'static str> {
+		let len = u32::decode(input)? as usize;
+		let bytes = read_bytes(input, len)?;
+		String::from_utf8(bytes.to_vec()).map_err(|_| "invalid utf8 string")
+	}
+}
+
+impl<T: Encode> Encode for Vec<T> {
+	fn encode_to(&self, dest: &mut Vec<u8>) {
+		(self.len() as u32).encode_to(dest);
+		self.iter().for_each(|item| item.encode_to(dest));
+	}
+}
+
+impl<T: Decode> Decode for Vec<T> {
+	fn decode(input: &mut &[u8]) -> Result<Self, &'static str> {
+		let len = u32::decode(input)? as usize;
+		(0..len).map(|_| T::decode(input)).collect()
+	}
+}
+
+/// Raw bytes, like a storage key or a remark.
+///
+/// Unlike `Vec<u8>`, these can be parsed from a hex string like `0x68656c6c6f`, so they can be used
+/// as the argument of a call. They are encoded just like `Vec<u8>`.
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Bytes(pub Vec<u8>);
+
+// Bytes are printed in hex, the same way they are parsed.
+impl core::fmt::Debug for Bytes {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.write_str("0x")?;
+		self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+	}
+}
+
+impl From<&str> for Bytes {
+	fn from(s: &str) -> Self {
+		Bytes(s.as_bytes().to_vec())
+	}
+}
+
+impl core::str::FromStr for Bytes {
+	type Err = &'static str;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let hex = s.strip_prefix("0x").ok_or("expected bytes like `0x1234`")?;
+		if hex.len() % 2 != 0 {
+			return Err("expected an even number of hex digits")
+		}
+		let digit = |c: u8| (c as char).to_digit(16).ok_or("invalid hex digit");
+		hex.as_bytes()
+			.chunks(2)
+			.map(|pair| Ok((digit(pair[0])? * 16 + digit(pair[1])?) as u8))
+			.collect::<Result<Vec<u8>, _>>()
+			.map(Bytes)
+	}
+}
+
+impl Encode for Bytes {
+	fn encode_to(&self, dest: &mut Vec<u8>) {
+		self.0.encode_to(dest);
+	}
+}
+
+impl Decode for Bytes {
+	fn decode(input: &mut &[u8]) -> Result<Self, &'static str> {
+		Vec::<u8>::decode(input).map(Bytes)
+	}
+}
+
+/// A 32 byte digest, like the output of `Sha256`.
+///
+/// Like `Bytes`, this is parsed from and printed as a hex string, but it always has exactly 32
+/// bytes, so it is encoded without a length prefix.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct H256(pub [u8; 32]);
+
+impl core::fmt::Debug for H256 {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		Bytes(self.0.to_vec()).fmt(f)
+	}
+}
+
+impl core::str::FromStr for H256 {
+	type Err = &'static str;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let bytes: Bytes = s.parse()?;
+		bytes.0.try_into().map(H256).map_err(|_| "expected 32 bytes")
+	}
+}
+
+impl Encode for H256 {
+	fn encode_to(&self, dest: &mut Vec<u8>) {
+		dest.extend_from_slice(&self.0);
+	}
+}
+
+impl Decode for H256 {
+	fn decode(input: &mut &[u8]) -> Result<Self, &'static str> {
+		let bytes = read_bytes(input, 32)?;
+		Ok(H256(bytes.try_into().expect("we read the right length")))
+	}
+}
+
+/// A hash function, which turns data of any length into a fixed size `Output`.
+pub trait Hash {
+	/// The type of the digest, like `H256`.
+	type Output;
+
+	/// Hash `data` into its digest.
+	fn hash(data: &[u8]) -> Self::Output;
+}
+
+/// The SHA-256 hash function.
+///
+/// On a real blockchain, you would use a well tested crate for this. We implement it ourselves,
+/// following FIPS 180-4, so that our state machine has no dependencies besides `num`.
+#[derive(Debug)]
+pub struct Sha256;
+
+impl Sha256 {
+	// The first 32 bits of the fractional parts of the cube roots of the first 64 primes.
+	const K: [u32; 64] = [
+		0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4,
+		0xab1c5ed5, 0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe,
+		0x9bdc06a7, 0xc19bf174, 0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f,
+		0x4a7484aa, 0x5cb0a9dc, 0x76f988da, 0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7,
+		0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967, 0x27b70a85, 0x2e1b2138, 0x4d2c6dfc,
+		0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85, 0xa2bfe8a1, 0xa81a664b,
+		0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070, 0x19a4c116,
+		0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+		0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7,
+		0xc67178f2,
+	];
+
+	// The first 32 bits of the fractional parts of the square roots of the first 8 primes.
+	const INITIAL_STATE: [u32; 8] = [
+		0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+		0x5be0cd19,
+	];
+
+	// Mix one 64 byte block of the message into `state`.
+	fn compress(state: &mut [u32; 8], block: &[u8]) {
+		let mut w = [0u32; 64];
+		for (word, bytes) in w.iter_mut().zip(block.chunks(4)) {
+			*word = u32::from_be_bytes(bytes.try_into().expect("blocks are 64 bytes"));
+		}
+		for i in 16..64 {
+			let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+			let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+			w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+		}
+
+		let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+		for (k, w) in Self::K.iter().zip(w) {
+			let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+			let ch = (e & f) ^ (!e & g);
+			let t1 = h.wrapping_add(s1).wrapping_add(ch).wrapping_add(*k).wrapping_add(w);
+			let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+			let maj = (a & b) ^ (a & c) ^ (b & c);
+			let t2 = s0.wrapping_add(maj);
+			(h, g, f, e, d, c, b, a) = (g, f, e, d.wrapping_add(t1), c, b, a, t1.wrapping_add(t2));
+		}
+		for (word, new) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+			*word = word.wrapping_add(new);
+		}
+	}
+}
+
+impl Hash for Sha256 {
+	type Output = H256;
+
+	fn hash(data: &[u8]) -> H256 {
+		// The message is padded with a single `1` bit, then zeros, then its length in bits, so
+		// that it is a whole number of 64 byte blocks.
+		let mut message = data.to_vec();
+		message.push(0x80);
+		while message.len() % 64 != 56 {
+			message.push(0);
+		}
+		message.extend_from_slice(&(data.len() as u64 * 8).to_be_bytes());
+
+		let mut state = Self::INITIAL_STATE;
+		message.chunks(64).for_each(|block| Self::compress(&mut state, block));
+
+		let mut digest = [0u8; 32];
+		for (bytes, word) in digest.chunks_mut(4).zip(state) {
+			bytes.copy_from_slice(&word.to_be_bytes());
+		}
+		H256(digest)
+	}
+}
@@ -1,10 +1,14 @@
+use crate::support::{Bytes, DispatchResult};
 use core::ops::AddAssign;
 use num::traits::{One, Zero};
 use std::collections::BTreeMap;
 
 /// The configuration trait for the System Pallet.
 /// This controls the common types used throughout our state machine.
-pub trait Config {
+///
+/// A runtime lives for the whole program, which lets `PalletInfo` identify pallets by their
+/// `TypeId`.
+pub trait Config: 'static {
 	/// A type which can identify an account in our state machine.
 	/// On a real blockchain, you would want this to be a cryptographic public key.
 	type AccountId: Ord + Clone;
@@ -13,7 +17,29 @@ pub trait Config {
 	type BlockNumber: Zero + One + AddAssign + Copy;
 	/// A type which can be used to keep track of the number of transactions from each account.
 	/// Usually a basic unsigned integer.
-	type Nonce: One + AddAssign + Default;
+	type Nonce: One + AddAssign + Default + Copy;
+	/// Information about where each pallet is placed in the runtime.
+	/// This is generated for the `Runtime` by `#[macros::runtime]`.
+	type PalletInfo: crate::support::PalletInfo;
+	/// The call which can be dispatched in the runtime.
+	/// This is the `RuntimeCall` generated by `#[macros::runtime]`.
+	type RuntimeCall;
+	/// The filter which decides which calls are allowed to be dispatched in the runtime.
+	/// Use `()` to allow every call.
+	type BaseCallFilter: crate::support::CallFilter<Self, Self::RuntimeCall>;
+	/// The root account, which is allowed to make privileged calls, like pausing transactions.
+	type RootAccount: crate::support::Get<Self::AccountId>;
+	/// The event which can be emitted in the runtime, which includes the events of every pallet.
+	/// A runtime without other events can use the `Event` of the System Pallet.
+	type RuntimeEvent: From<Event<Self>> + core::fmt::Debug;
+}
+
+/// Check that `who` is the root account, which is allowed to make privileged calls.
+pub fn ensure_root<T: Config>(who: &T::AccountId) -> crate::support::DispatchResult {
+	if *who != <T::RootAccount as crate::support::Get<T::AccountId>>::get() {
+		return Err(crate::support::DispatchError::BadOrigin)
+	}
+	Ok(())
 }
 
 /// This is the System Pallet.
@@ -24,6 +50,17 @@ pub struct Pallet<T: Config> {
 	block_number: T::BlockNumber,
 	/// A map from an account to their nonce.
 	nonce: BTreeMap<T::AccountId, T::Nonce>,
+	/// Raw storage, from a key to a value. Only root can change this storage.
+	storage: BTreeMap<Bytes, Bytes>,
+	/// The events emitted by every pallet during the current block.
+	events: Vec<T::RuntimeEvent>,
+}
+
+/// The events which can be emitted by the System Pallet.
+#[derive(Debug)]
+pub enum Event<T: Config + ?Sized> {
+	/// An account made a remark.
+	Remarked { sender: T::AccountId, data: Bytes },
 }
 
 /// The System Pallet is a low level system which is not really meant to be exposed to the outside
@@ -31,7 +68,12 @@ pub struct Pallet<T: Config> {
 impl<T: Config> Pallet<T> {
 	/// Create a new instance of the System Pallet.
 	pub fn new() -> Self {
-		Self { block_number: T::BlockNumber::zero(), nonce: BTreeMap::new() }
+		Self {
+			block_number: T::BlockNumber::zero(),
+			nonce: BTreeMap::new(),
+			storage: BTreeMap::new(),
+			events: Vec::new(),
+		}
 	}
 
 	/// Get the current block number.
@@ -40,9 +82,10 @@ impl<T: Config> Pallet<T> {
 	}
 
 	// This function can be used to increment the block number.
-	// Increases the block number by one.
+	// Increases the block number by one, and clears the events of the previous block.
 	pub fn inc_block_number(&mut self) {
 		self.block_number += T::BlockNumber::one();
+		self.events.clear();
 	}
 
 	// Increment the nonce of an account. This helps us keep track of how many transactions each
@@ -50,15 +93,107 @@ impl<T: Config> Pallet<T> {
 	pub fn inc_nonce(&mut self, who: &T::AccountId) {
 		*self.nonce.entry(who.clone()).or_default() += T::Nonce::one();
 	}
+
+	/// Get the nonce of an account `who`.
+	/// If the account has no stored nonce, we return the default, usually zero.
+	pub fn nonce(&self, who: &T::AccountId) -> T::Nonce {
+		self.nonce.get(who).copied().unwrap_or_default()
+	}
+
+	/// Get the value stored under a raw storage `key`.
+	pub fn storage(&self, key: &Bytes) -> Option<&Bytes> {
+		self.storage.get(key)
+	}
+
+	/// Get the events emitted by every pallet during the current block.
+	pub fn events(&self) -> &[T::RuntimeEvent] {
+		&self.events
+	}
+
+	/// Emit an event during the current block. Other pallets emit their own events here too.
+	pub fn deposit_event(&mut self, event: impl Into<T::RuntimeEvent>) {
+		self.events.push(event.into());
+	}
+}
+
+// When an account is reaped by another pallet, like balances, we remove its nonce.
+impl<T> crate::support::OnKilledAccount<T, T::AccountId> for Pallet<T>
+where
+	T: Config + crate::support::GetPallet<Self>,
+{
+	fn on_killed_account(runtime: &mut T, who: &T::AccountId) {
+		crate::support::GetPallet::<Self>::pallet_mut(runtime).nonce.remove(who);
+	}
+}
+
+// The System Pallet also exposes a few calls for administration and for anchoring data on chain.
+#[macros::call]
+impl<T: Config> Pallet<T> {
+	/// Make a remark on chain. This does nothing, but the remark is kept in the block.
+	#[call_index(0)]
+	pub fn remark(&mut self, _caller: T::AccountId, _data: Bytes) -> DispatchResult {
+		Ok(())
+	}
+
+	/// Set the value stored under a raw storage `key`.
+	/// Only root can make this call.
+	#[call_index(1)]
+	pub fn set_storage(
+		&mut self,
+		caller: T::AccountId,
+		key: Bytes,
+		value: Bytes,
+	) -> DispatchResult {
+		ensure_root::<T>(&caller)?;
+		self.storage.insert(key, value);
+		Ok(())
+	}
+
+	/// Remove the value stored under a raw storage `key`.
+	/// Only root can make this call.
+	#[call_index(2)]
+	pub fn kill_storage(&mut self, caller: T::AccountId, key: Bytes) -> DispatchResult {
+		ensure_root::<T>(&caller)?;
+		self.storage.remove(&key);
+		Ok(())
+	}
+
+	/// Remove every value stored under a raw storage key starting with `prefix`.
+	/// Only root can make this call.
+	#[call_index(3)]
+	pub fn kill_prefix(&mut self, caller: T::AccountId, prefix: Bytes) -> DispatchResult {
+		ensure_root::<T>(&caller)?;
+		self.storage.retain(|key, _| !key.0.starts_with(&prefix.0));
+		Ok(())
+	}
+
+	/// Make a remark on chain, and emit an event with the remark.
+	#[call_index(4)]
+	pub fn remark_with_event(&mut self, caller: T::AccountId, data: Bytes) -> DispatchResult {
+		self.deposit_event(Event::Remarked { sender: caller, data });
+		Ok(())
+	}
 }
 
 #[cfg(test)]
 mod test {
+	use crate::support::{parameter_types, Bytes, DispatchError};
+
+	parameter_types! {
+		pub RootAccount: String = "root".to_string();
+	}
+
+	#[derive(Debug)]
 	struct TestConfig;
 	impl super::Config for TestConfig {
 		type AccountId = String;
 		type BlockNumber = u32;
 		type Nonce = u32;
+		type PalletInfo = ();
+		type RuntimeCall = ();
+		type BaseCallFilter = ();
+		type RootAccount = RootAccount;
+		type RuntimeEvent = super::Event<Self>;
 	}
 
 	#[test]
@@ -71,4 +206,67 @@ mod test {
 		assert_eq!(system.nonce.get("alice"), Some(&1));
 		assert_eq!(system.nonce.get("bob"), None);
 	}
+
+	#[test]
+	fn ensure_root() {
+		assert_eq!(super::ensure_root::<TestConfig>(&"root".to_string()), Ok(()));
+		assert_eq!(
+			super::ensure_root::<TestConfig>(&"alice".to_string()),
+			Err(DispatchError::BadOrigin)
+		);
+	}
+
+	#[test]
+	fn root_storage() {
+		let mut system = super::Pallet::<TestConfig>::new();
+		let root = "root".to_string();
+		let (key, other_key, value) =
+			(Bytes::from("a:1"), Bytes::from("b:1"), Bytes::from("value"));
+
+		assert_eq!(
+			system.set_storage("alice".to_string(), key.clone(), value.clone()),
+			Err(DispatchError::BadOrigin)
+		);
+		assert_eq!(system.storage(&key), None);
+
+		assert_eq!(system.set_storage(root.clone(), key.clone(), value.clone()), Ok(()));
+		assert_eq!(system.set_storage(root.clone(), other_key.clone(), value.clone()), Ok(()));
+		assert_eq!(system.storage(&key), Some(&value));
+
+		assert_eq!(system.kill_prefix(root.clone(), Bytes::from("a:")), Ok(()));
+		assert_eq!(system.storage(&key), None);
+		assert_eq!(system.storage(&other_key), Some(&value));
+
+		assert_eq!(system.kill_storage(root, other_key.clone()), Ok(()));
+		assert_eq!(system.storage(&other_key), None);
+	}
+
+	#[test]
+	fn remark_with_event() {
+		let mut system = super::Pallet::<TestConfig>::new();
+		assert_eq!(system.remark("alice".to_string(), Bytes::from("hello")), Ok(()));
+		assert!(system.events().is_empty());
+
+		assert_eq!(system.remark_with_event("alice".to_string(), Bytes::from("hello")), Ok(()));
+		assert!(matches!(
+			system.events(),
+			[super::Event::Remarked { sender, data }] if sender == "alice" && data.0 == b"hello"
+		));
+
+		// Events are cleared at the start of every block.
+		system.inc_block_number();
+		assert!(system.events().is_empty());
+	}
+
+	#[test]
+	fn parse_remark_call() {
+		let call: super::Call<TestConfig> = "remark_with_event data=0x68656c6c6f".parse().unwrap();
+		assert_eq!(call, super::Call::remark_with_event { data: Bytes::from("hello") });
+
+		assert_eq!("0x68656c6c6f".parse(), Ok(Bytes::from("hello")));
+		assert_eq!("0x".parse(), Ok(Bytes::default()));
+		assert!("68656c6c6f".parse::<Bytes>().is_err());
+		assert!("0x686".parse::<Bytes>().is_err());
+		assert!("0xzz".parse::<Bytes>().is_err());
+	}
 }
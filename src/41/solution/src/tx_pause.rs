@@ -0,0 +1,415 @@
+use crate::{
+	support::{CallFilter, CallMetadata, DispatchResult, Get, GetPallet, PalletInfo},
+	system::ensure_root,
+};
+use std::collections::BTreeSet;
+
+pub trait Config: crate::system::Config<RuntimeCall: CallMetadata> {
+	/// The number of blocks safe mode lasts after the block it is entered in, unless root exits it
+	/// earlier.
+	type SafeModeDuration: Get<u32>;
+	/// The calls which are still allowed while safe mode is active, as `(pallet, call)` names.
+	type SafeModeWhitelist: Get<Vec<(&'static str, &'static str)>>;
+}
+
+/// This is the Transaction Pause Module.
+/// It allows the root account to pause whole pallets or specific calls, and to put the runtime
+/// into safe mode, where only whitelisted calls are allowed.
+///
+/// To take effect, this module must be used as the `BaseCallFilter` of the runtime. The calls of
+/// this module can never be paused, so that root can always undo a pause.
+#[derive(Debug)]
+pub struct Pallet<T: Config> {
+	/// The pallets which are paused, by name.
+	paused_pallets: BTreeSet<String>,
+	/// The calls which are paused, by pallet name and call name.
+	paused_calls: BTreeSet<(String, String)>,
+	/// The number of blocks which start before safe mode ends, if safe mode is active.
+	safe_mode_blocks_left: Option<u32>,
+	/// This module does not store anything of type `T`.
+	_config: core::marker::PhantomData<T>,
+}
+
+impl<T: Config> Pallet<T> {
+	/// Create a new instance of the Transaction Pause Module.
+	pub fn new() -> Self {
+		Self {
+			paused_pallets: BTreeSet::new(),
+			paused_calls: BTreeSet::new(),
+			safe_mode_blocks_left: None,
+			_config: core::marker::PhantomData,
+		}
+	}
+
+	/// Check if safe mode is active.
+	pub fn is_safe_mode(&self) -> bool {
+		self.safe_mode_blocks_left.is_some()
+	}
+
+	/// Check if a call is allowed, given the paused pallets and calls, and safe mode.
+	pub fn is_allowed(&self, call: &impl CallMetadata) -> bool {
+		let (pallet, call) = (call.pallet_name(), call.call_name());
+
+		// The calls of this module are always allowed, so that root can always undo a pause.
+		if Self::name() == Some(pallet) {
+			return true
+		}
+
+		if self.is_safe_mode() {
+			return T::SafeModeWhitelist::get().contains(&(pallet, call))
+		}
+
+		!self.paused_pallets.contains(pallet) &&
+			!self.paused_calls.contains(&(pallet.to_string(), call.to_string()))
+	}
+
+	/// The name of this module in the runtime, if it is part of one.
+	fn name() -> Option<&'static str> {
+		<T::PalletInfo as PalletInfo>::name::<Self>()
+	}
+}
+
+// Safe mode ends automatically at the start of the block after the last of its
+// `SafeModeDuration` blocks.
+impl<T> crate::support::Hooks<T, T::BlockNumber> for Pallet<T>
+where
+	T: Config + GetPallet<Self>,
+{
+	fn on_initialize(runtime: &mut T, _block_number: T::BlockNumber) {
+		let tx_pause = GetPallet::<Self>::pallet_mut(runtime);
+		tx_pause.safe_mode_blocks_left = match tx_pause.safe_mode_blocks_left {
+			Some(left) if left > 1 => Some(left - 1),
+			_ => None,
+		};
+	}
+}
+
+// The Transaction Pause Module can be used as the `BaseCallFilter` of the runtime. It reads the
+// paused calls from its own storage in the runtime.
+impl<T, Call> CallFilter<T, Call> for Pallet<T>
+where
+	T: Config + GetPallet<Pallet<T>>,
+	Call: CallMetadata,
+{
+	fn allows(runtime: &T, call: &Call) -> bool {
+		GetPallet::<Pallet<T>>::pallet(runtime).is_allowed(call)
+	}
+}
+
+/// The errors which can be returned by the Transaction Pause Module.
+#[macros::error]
+pub enum Error<T> {
+	/// This pallet or call is already paused.
+	AlreadyPaused,
+	/// This pallet or call is not paused.
+	NotPaused,
+	/// The calls of the transaction pause module cannot be paused.
+	Unpausable,
+	/// Safe mode is already active.
+	AlreadyInSafeMode,
+	/// Safe mode is not active.
+	NotInSafeMode,
+	/// There is no pallet with this name in the runtime.
+	UnknownPallet,
+	/// The pallet has no call with this name.
+	UnknownCall,
+}
+
+#[macros::call]
+impl<T: Config> Pallet<T> {
+	/// Pause the call named `call` of the pallet named `pallet`.
+	/// Only root can make this call.
+	#[call_index(0)]
+	pub fn pause(&mut self, caller: T::AccountId, pallet: String, call: String) -> DispatchResult {
+		ensure_root::<T>(&caller)?;
+		self.ensure_pausable(&pallet, Some(&call))?;
+		if !self.paused_calls.insert((pallet, call)) {
+			return Err(Error::<T>::AlreadyPaused.into())
+		}
+		Ok(())
+	}
+
+	/// Unpause the call named `call` of the pallet named `pallet`.
+	/// Only root can make this call.
+	#[call_index(1)]
+	pub fn unpause(
+		&mut self,
+		caller: T::AccountId,
+		pallet: String,
+		call: String,
+	) -> DispatchResult {
+		ensure_root::<T>(&caller)?;
+		if !self.paused_calls.remove(&(pallet, call)) {
+			return Err(Error::<T>::NotPaused.into())
+		}
+		Ok(())
+	}
+
+	/// Pause all the calls of the pallet named `pallet`.
+	/// Only root can make this call.
+	#[call_index(2)]
+	pub fn pause_pallet(&mut self, caller: T::AccountId, pallet: String) -> DispatchResult {
+		ensure_root::<T>(&caller)?;
+		self.ensure_pausable(&pallet, None)?;
+		if !self.paused_pallets.insert(pallet) {
+			return Err(Error::<T>::AlreadyPaused.into())
+		}
+		Ok(())
+	}
+
+	/// Unpause all the calls of the pallet named `pallet`. Calls which were paused one by one stay
+	/// paused.
+	/// Only root can make this call.
+	#[call_index(3)]
+	pub fn unpause_pallet(&mut self, caller: T::AccountId, pallet: String) -> DispatchResult {
+		ensure_root::<T>(&caller)?;
+		if !self.paused_pallets.remove(&pallet) {
+			return Err(Error::<T>::NotPaused.into())
+		}
+		Ok(())
+	}
+
+	/// Enter safe mode, where only the calls in `SafeModeWhitelist` are allowed.
+	/// Safe mode lasts for the rest of this block, and then `SafeModeDuration` more blocks.
+	/// Only root can make this call.
+	#[call_index(4)]
+	pub fn enter_safe_mode(&mut self, caller: T::AccountId) -> DispatchResult {
+		ensure_root::<T>(&caller)?;
+		if self.is_safe_mode() {
+			return Err(Error::<T>::AlreadyInSafeMode.into())
+		}
+		self.safe_mode_blocks_left = Some(T::SafeModeDuration::get().saturating_add(1));
+		Ok(())
+	}
+
+	/// Exit safe mode before it ends by itself.
+	/// Only root can make this call.
+	#[call_index(5)]
+	pub fn exit_safe_mode(&mut self, caller: T::AccountId) -> DispatchResult {
+		ensure_root::<T>(&caller)?;
+		if !self.is_safe_mode() {
+			return Err(Error::<T>::NotInSafeMode.into())
+		}
+		self.safe_mode_blocks_left = None;
+		Ok(())
+	}
+
+	/// Check that the pallet named `pallet`, and its call named `call` if one is given, are part of
+	/// the runtime and can be paused.
+	/// This is a helper function, and is not callable.
+	#[call(skip)]
+	fn ensure_pausable(&self, pallet: &str, call: Option<&str>) -> DispatchResult {
+		let calls = T::RuntimeCall::call_names(pallet).ok_or(Error::<T>::UnknownPallet)?;
+		if call.is_some_and(|call| !calls.contains(&call)) {
+			return Err(Error::<T>::UnknownCall.into())
+		}
+		if Self::name() == Some(pallet) {
+			return Err(Error::<T>::Unpausable.into())
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Error;
+	use crate::{
+		mock::{execute_next_block, new_test_runtime, RuntimeCall, TestRuntime},
+		support::{DispatchError, Hooks},
+	};
+
+	type TxPause = super::Pallet<TestRuntime>;
+
+	// Parse a runtime call, like `balances.transfer to=bob amount=1`.
+	fn call(call: &str) -> RuntimeCall {
+		call.parse().unwrap()
+	}
+
+	#[test]
+	fn pause_and_unpause_calls() {
+		let mut tx_pause = super::Pallet::<TestRuntime>::new();
+		let root = "root".to_string();
+		let claim = call("proof_of_existence.create_claim claim=hello");
+
+		assert!(tx_pause.is_allowed(&claim));
+		assert_eq!(
+			tx_pause.pause(root.clone(), "proof_of_existence".into(), "create_claim".into()),
+			Ok(())
+		);
+		assert!(!tx_pause.is_allowed(&claim));
+		assert!(tx_pause.is_allowed(&call("proof_of_existence.revoke_claim claim=hello")));
+		assert_eq!(
+			tx_pause.pause(root.clone(), "proof_of_existence".into(), "create_claim".into()),
+			Err(Error::<TestRuntime>::AlreadyPaused.into())
+		);
+
+		assert_eq!(
+			tx_pause.unpause(root.clone(), "proof_of_existence".into(), "create_claim".into()),
+			Ok(())
+		);
+		assert!(tx_pause.is_allowed(&claim));
+		assert_eq!(
+			tx_pause.unpause(root, "proof_of_existence".into(), "create_claim".into()),
+			Err(Error::<TestRuntime>::NotPaused.into())
+		);
+	}
+
+	#[test]
+	fn pause_and_unpause_pallets() {
+		let mut tx_pause = super::Pallet::<TestRuntime>::new();
+		let root = "root".to_string();
+		let transfer = call("balances.transfer to=bob amount=1");
+
+		assert_eq!(tx_pause.pause_pallet(root.clone(), "balances".into()), Ok(()));
+		assert!(!tx_pause.is_allowed(&transfer));
+		assert!(tx_pause.is_allowed(&call("proof_of_existence.create_claim claim=hello")));
+
+		assert_eq!(tx_pause.unpause_pallet(root.clone(), "balances".into()), Ok(()));
+		assert!(tx_pause.is_allowed(&transfer));
+		assert_eq!(
+			tx_pause.unpause_pallet(root, "balances".into()),
+			Err(Error::<TestRuntime>::NotPaused.into())
+		);
+	}
+
+	#[test]
+	fn only_known_pallets_and_calls_can_be_paused() {
+		let mut tx_pause = super::Pallet::<TestRuntime>::new();
+		let root = "root".to_string();
+
+		assert_eq!(
+			tx_pause.pause_pallet(root.clone(), "balance".into()),
+			Err(Error::<TestRuntime>::UnknownPallet.into())
+		);
+		assert_eq!(
+			tx_pause.pause(root.clone(), "balance".into(), "transfer".into()),
+			Err(Error::<TestRuntime>::UnknownPallet.into())
+		);
+		assert_eq!(
+			tx_pause.pause(root.clone(), "balances".into(), "create_claim".into()),
+			Err(Error::<TestRuntime>::UnknownCall.into())
+		);
+		// The calls of this pallet are known, but can never be paused.
+		assert_eq!(
+			tx_pause.pause_pallet(root.clone(), "tx_pause".into()),
+			Err(Error::<TestRuntime>::Unpausable.into())
+		);
+		assert_eq!(
+			tx_pause.pause(root, "tx_pause".into(), "unpause_pallet".into()),
+			Err(Error::<TestRuntime>::Unpausable.into())
+		);
+	}
+
+	#[test]
+	fn only_root_can_pause() {
+		let mut tx_pause = super::Pallet::<TestRuntime>::new();
+		let alice = "alice".to_string();
+
+		assert_eq!(
+			tx_pause.pause(alice.clone(), "balances".into(), "transfer".into()),
+			Err(DispatchError::BadOrigin)
+		);
+		assert_eq!(
+			tx_pause.pause_pallet(alice.clone(), "balances".into()),
+			Err(DispatchError::BadOrigin)
+		);
+		assert_eq!(tx_pause.enter_safe_mode(alice), Err(DispatchError::BadOrigin));
+		assert!(tx_pause.is_allowed(&call("balances.transfer to=bob amount=1")));
+	}
+
+	#[test]
+	fn safe_mode_only_allows_whitelisted_calls_and_ends_by_itself() {
+		let mut runtime = new_test_runtime();
+		let root = "root".to_string();
+		let transfer = call("balances.transfer to=bob amount=1");
+		let claim = call("proof_of_existence.create_claim claim=hello");
+
+		assert_eq!(runtime.tx_pause.enter_safe_mode(root.clone()), Ok(()));
+		assert_eq!(
+			runtime.tx_pause.enter_safe_mode(root.clone()),
+			Err(Error::<TestRuntime>::AlreadyInSafeMode.into())
+		);
+		assert!(runtime.tx_pause.is_allowed(&transfer));
+		assert!(!runtime.tx_pause.is_allowed(&claim));
+
+		// Safe mode lasts for two blocks after the one it was entered in.
+		TxPause::on_initialize(&mut runtime, 1);
+		assert!(runtime.tx_pause.is_safe_mode());
+		TxPause::on_initialize(&mut runtime, 2);
+		assert!(runtime.tx_pause.is_safe_mode());
+		TxPause::on_initialize(&mut runtime, 3);
+		assert!(!runtime.tx_pause.is_safe_mode());
+		assert!(runtime.tx_pause.is_allowed(&claim));
+
+		// Root can also exit safe mode early.
+		assert_eq!(runtime.tx_pause.enter_safe_mode(root.clone()), Ok(()));
+		assert_eq!(runtime.tx_pause.exit_safe_mode(root.clone()), Ok(()));
+		assert!(runtime.tx_pause.is_allowed(&claim));
+		assert_eq!(
+			runtime.tx_pause.exit_safe_mode(root),
+			Err(Error::<TestRuntime>::NotInSafeMode.into())
+		);
+	}
+
+	#[test]
+	fn root_can_unpause_after_pausing_every_other_pallet() {
+		let mut runtime = new_test_runtime();
+		let (root, alice) = ("root".to_string(), "alice".to_string());
+		runtime.balances.set_balance(&alice, 100);
+
+		// Root pauses every pallet in the runtime, through the runtime itself. Pausing this
+		// pallet fails, so it stays callable.
+		let pallets = [
+			"system",
+			"balances",
+			"proof_of_existence",
+			"loyalty",
+			"tx_pause",
+			"vesting",
+			"assets",
+		];
+		let pauses = pallets
+			.iter()
+			.map(|pallet| (root.clone(), call(&format!("tx_pause.pause_pallet pallet={pallet}"))))
+			.collect();
+		assert_eq!(execute_next_block(&mut runtime, pauses), Ok(()));
+		let transfer = call("balances.transfer to=bob amount=10");
+		assert!(!runtime.tx_pause.is_allowed(&transfer));
+		assert!(runtime.tx_pause.is_allowed(&call("tx_pause.unpause_pallet pallet=balances")));
+
+		// Calls to the paused pallets are filtered.
+		assert_eq!(
+			execute_next_block(&mut runtime, vec![(alice.clone(), transfer.clone())]),
+			Ok(())
+		);
+		assert_eq!(runtime.balances.balance(&alice), 100);
+
+		// Root can still unpause a pallet, which makes its calls work again.
+		let unpause = call("tx_pause.unpause_pallet pallet=balances");
+		assert_eq!(
+			execute_next_block(&mut runtime, vec![(root, unpause), (alice.clone(), transfer)]),
+			Ok(())
+		);
+		assert_eq!(runtime.balances.balance(&alice), 90);
+		assert_eq!(runtime.balances.balance(&"bob".to_string()), 10);
+	}
+
+	#[test]
+	fn safe_mode_lasts_for_the_rest_of_its_block_and_then_the_duration() {
+		let mut runtime = new_test_runtime();
+		let root = "root".to_string();
+
+		// Safe mode is entered in block 1, so with a duration of two, block 3 is its last.
+		let enter = call("tx_pause.enter_safe_mode");
+		assert_eq!(execute_next_block(&mut runtime, vec![(root, enter)]), Ok(()));
+		assert!(runtime.tx_pause.is_safe_mode());
+		for _ in 2..=3 {
+			assert_eq!(execute_next_block(&mut runtime, vec![]), Ok(()));
+			assert!(runtime.tx_pause.is_safe_mode());
+		}
+		assert_eq!(runtime.system.block_number(), 3);
+
+		assert_eq!(execute_next_block(&mut runtime, vec![]), Ok(()));
+		assert!(!runtime.tx_pause.is_safe_mode());
+	}
+}
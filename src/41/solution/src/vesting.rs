@@ -0,0 +1,519 @@
+use crate::support::{
+	Currency, Decode, DispatchError, DispatchResult, Encode, Fungible, Get, GetPallet,
+	LockIdentifier, LockableCurrency,
+};
+use core::fmt::Debug;
+use num::traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, One, Zero};
+use std::collections::BTreeMap;
+
+/// The name of the lock which keeps unvested funds from being transferred.
+const VESTING_ID: LockIdentifier = *b"vesting ";
+
+/// The configuration trait for the Vesting Module.
+pub trait Config: crate::system::Config {
+	/// A type which can represent the balance of an account, used by the `Currency`.
+	/// Block numbers are converted into this type to work out how much has vested.
+	type Balance: Zero
+		+ One
+		+ CheckedAdd
+		+ CheckedSub
+		+ CheckedMul
+		+ CheckedDiv
+		+ Copy
+		+ PartialOrd
+		+ Debug
+		+ From<Self::BlockNumber>;
+	/// The currency which is locked while it vests.
+	type Currency: Currency<Self, Self::AccountId, Balance = Self::Balance>;
+	/// The minimum amount which can be transferred with a vesting schedule.
+	type MinVestedTransfer: Get<Self::Balance>;
+	/// The maximum number of vesting schedules an account can have.
+	type MaxVestingSchedules: Get<u32>;
+}
+
+/// A vesting schedule: `locked` funds which are released by `per_block` every block, starting at
+/// `starting_block`.
+///
+/// A schedule can be written as `locked,per_block,starting_block`, like `100,10,5`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VestingInfo<Balance, BlockNumber> {
+	/// The amount locked at the start of the schedule.
+	pub locked: Balance,
+	/// The amount released every block after the start of the schedule.
+	pub per_block: Balance,
+	/// The block at which funds start being released.
+	pub starting_block: BlockNumber,
+}
+
+impl<Balance: Encode, BlockNumber: Encode> Encode for VestingInfo<Balance, BlockNumber> {
+	fn encode_to(&self, dest: &mut Vec<u8>) {
+		self.locked.encode_to(dest);
+		self.per_block.encode_to(dest);
+		self.starting_block.encode_to(dest);
+	}
+}
+
+impl<Balance: Decode, BlockNumber: Decode> Decode for VestingInfo<Balance, BlockNumber> {
+	fn decode(input: &mut &[u8]) -> Result<Self, &'static str> {
+		Ok(Self {
+			locked: Balance::decode(input)?,
+			per_block: Balance::decode(input)?,
+			starting_block: BlockNumber::decode(input)?,
+		})
+	}
+}
+
+impl<Balance: core::str::FromStr, BlockNumber: core::str::FromStr> core::str::FromStr
+	for VestingInfo<Balance, BlockNumber>
+{
+	type Err = &'static str;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let msg = "expected a schedule like `locked,per_block,starting_block`";
+		let mut parts = s.split(',');
+		let (Some(locked), Some(per_block), Some(starting_block), None) =
+			(parts.next(), parts.next(), parts.next(), parts.next())
+		else {
+			return Err(msg)
+		};
+		Ok(Self {
+			locked: locked.trim().parse().map_err(|_| msg)?,
+			per_block: per_block.trim().parse().map_err(|_| msg)?,
+			starting_block: starting_block.trim().parse().map_err(|_| msg)?,
+		})
+	}
+}
+
+/// A vesting schedule of the Vesting Module.
+pub type VestingInfoOf<T> =
+	VestingInfo<<T as Config>::Balance, <T as crate::system::Config>::BlockNumber>;
+
+/// This is the Vesting Module.
+/// It releases funds to accounts over time. Funds which have not vested yet are kept from being
+/// transferred by a lock on the `Currency`.
+pub struct Pallet<T: Config> {
+	/// The vesting schedules of each account.
+	vesting: BTreeMap<T::AccountId, Vec<VestingInfoOf<T>>>,
+}
+
+// `#[derive(Debug)]` would not know that the schedules need `T::BlockNumber: Debug`.
+impl<T: Config> Debug for Pallet<T>
+where
+	T::AccountId: Debug,
+	T::BlockNumber: Debug,
+{
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("Pallet").field("vesting", &self.vesting).finish()
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Create a new instance of the Vesting Module.
+	pub fn new() -> Self {
+		Self { vesting: BTreeMap::new() }
+	}
+
+	/// Get the vesting schedules of an account `who`.
+	pub fn vesting(&self, who: &T::AccountId) -> &[VestingInfoOf<T>] {
+		self.vesting.get(who).map(Vec::as_slice).unwrap_or_default()
+	}
+
+	/// Get the amount of a `schedule` which is still locked at block `now`.
+	pub fn locked_at(schedule: &VestingInfoOf<T>, now: T::BlockNumber) -> T::Balance {
+		let now = T::Balance::from(now);
+		let start = T::Balance::from(schedule.starting_block);
+		let Some(elapsed) = now.checked_sub(&start) else { return schedule.locked };
+		// If this overflows, far more than `locked` has vested.
+		let vested = schedule.per_block.checked_mul(&elapsed).unwrap_or(schedule.locked);
+		schedule.locked.checked_sub(&vested).unwrap_or(T::Balance::zero())
+	}
+
+	// The block at which a `schedule` has fully vested, as a balance.
+	fn ending_block(schedule: &VestingInfoOf<T>) -> Option<T::Balance> {
+		let per_block =
+			if schedule.per_block.is_zero() { T::Balance::one() } else { schedule.per_block };
+		let mut duration = schedule.locked.checked_div(&per_block)?;
+		if duration.checked_mul(&per_block)? < schedule.locked {
+			duration = duration.checked_add(&T::Balance::one())?;
+		}
+		T::Balance::from(schedule.starting_block).checked_add(&duration)
+	}
+
+	// Merge two schedules which are still vesting at block `now` into one, which starts at the
+	// latest start, ends at the latest end, and locks what is left of both.
+	fn merge(
+		first: &VestingInfoOf<T>,
+		second: &VestingInfoOf<T>,
+		now: T::BlockNumber,
+	) -> Option<VestingInfoOf<T>> {
+		let locked = Self::locked_at(first, now).checked_add(&Self::locked_at(second, now))?;
+		let ending_block = {
+			let (first, second) = (Self::ending_block(first)?, Self::ending_block(second)?);
+			if first > second {
+				first
+			} else {
+				second
+			}
+		};
+		// Block numbers are only compared as balances, so we pick the latest one that way.
+		let starting_block =
+			[first.starting_block, second.starting_block]
+				.into_iter()
+				.fold(now, |latest, block| {
+					if T::Balance::from(block) > T::Balance::from(latest) {
+						block
+					} else {
+						latest
+					}
+				});
+		let duration = ending_block
+			.checked_sub(&T::Balance::from(starting_block))
+			.filter(|duration| !duration.is_zero())
+			.unwrap_or(T::Balance::one());
+		let per_block = locked.checked_div(&duration)?;
+		let per_block = if per_block.is_zero() { T::Balance::one() } else { per_block };
+		Some(VestingInfo { locked, per_block, starting_block })
+	}
+
+	// Find the schedules which are still vesting at block `now`, and the total amount they lock.
+	// This does not change any storage, so callers can check everything before they write.
+	fn still_vesting(
+		schedules: impl IntoIterator<Item = VestingInfoOf<T>>,
+		now: T::BlockNumber,
+	) -> Result<(Vec<VestingInfoOf<T>>, T::Balance), DispatchError> {
+		let mut locked = T::Balance::zero();
+		let mut still_vesting = Vec::new();
+		for schedule in schedules {
+			let schedule_locked = Self::locked_at(&schedule, now);
+			if !schedule_locked.is_zero() {
+				locked = locked.checked_add(&schedule_locked).ok_or(Error::<T>::Overflow)?;
+				still_vesting.push(schedule);
+			}
+		}
+		Ok((still_vesting, locked))
+	}
+
+	// Store the `schedules` of `who` which are still vesting, and lock the amount they lock.
+	fn set_schedules(
+		runtime: &mut T,
+		who: &T::AccountId,
+		schedules: Vec<VestingInfoOf<T>>,
+		locked: T::Balance,
+	) where
+		T: GetPallet<Self> + GetPallet<T::Currency>,
+	{
+		let vesting = GetPallet::<Self>::pallet_mut(runtime);
+		if schedules.is_empty() {
+			vesting.vesting.remove(who);
+		} else {
+			vesting.vesting.insert(who.clone(), schedules);
+		}
+
+		let currency = GetPallet::<T::Currency>::pallet_mut(runtime);
+		if locked.is_zero() {
+			currency.remove_lock(VESTING_ID, who);
+		} else {
+			currency.set_lock(VESTING_ID, who, locked);
+		}
+	}
+
+	// Remove the schedules of `who` which have fully vested, and lock what is left. Returns an
+	// error if `who` has no schedules.
+	fn update_lock(runtime: &mut T, who: &T::AccountId) -> DispatchResult
+	where
+		T: GetPallet<Self> + GetPallet<T::Currency> + GetPallet<crate::system::Pallet<T>>,
+	{
+		let now = GetPallet::<crate::system::Pallet<T>>::pallet(runtime).block_number();
+		let schedules = GetPallet::<Self>::pallet(runtime)
+			.vesting
+			.get(who)
+			.ok_or(Error::<T>::NotVesting)?;
+		let (schedules, locked) = Self::still_vesting(schedules.iter().copied(), now)?;
+		Self::set_schedules(runtime, who, schedules, locked);
+		Ok(())
+	}
+}
+
+// The vesting module does not need to do anything at the start of a block.
+impl<T: Config> crate::support::Hooks<T, T::BlockNumber> for Pallet<T> {}
+
+/// The errors which can be returned by the Vesting Module.
+#[macros::error]
+pub enum Error<T> {
+	/// The account has no vesting schedules.
+	NotVesting,
+	/// The account already has the maximum number of vesting schedules.
+	AtMaxVestingSchedules,
+	/// The amount to vest is less than the minimum vested transfer.
+	AmountLow,
+	/// A vesting schedule must release a non-zero amount every block.
+	InvalidScheduleParams,
+	/// There is no vesting schedule at this index.
+	ScheduleIndexOutOfBounds,
+	/// The locked amount of the schedules would overflow.
+	Overflow,
+}
+
+#[macros::call]
+impl<T: Config> Pallet<T> {
+	/// Unlock any funds of the caller which have vested.
+	#[call_index(0)]
+	pub fn vest(runtime: &mut T, caller: T::AccountId) -> DispatchResult
+	where
+		T: GetPallet<Self> + GetPallet<T::Currency> + GetPallet<crate::system::Pallet<T>>,
+	{
+		Self::update_lock(runtime, &caller)
+	}
+
+	/// Transfer `schedule.locked` to `target`, which is released to them following `schedule`.
+	#[call_index(1)]
+	pub fn vested_transfer(
+		runtime: &mut T,
+		caller: T::AccountId,
+		target: T::AccountId,
+		schedule: VestingInfoOf<T>,
+	) -> DispatchResult
+	where
+		T: GetPallet<Self> + GetPallet<T::Currency> + GetPallet<crate::system::Pallet<T>>,
+	{
+		if schedule.locked < T::MinVestedTransfer::get() {
+			return Err(Error::<T>::AmountLow.into())
+		}
+		if schedule.locked.is_zero() || schedule.per_block.is_zero() {
+			return Err(Error::<T>::InvalidScheduleParams.into())
+		}
+		let vesting = GetPallet::<Self>::pallet(runtime);
+		let schedules = vesting.vesting(&target);
+		if schedules.len() >= T::MaxVestingSchedules::get() as usize {
+			return Err(Error::<T>::AtMaxVestingSchedules.into())
+		}
+		let now = GetPallet::<crate::system::Pallet<T>>::pallet(runtime).block_number();
+		let (schedules, locked) =
+			Self::still_vesting(schedules.iter().copied().chain([schedule]), now)?;
+
+		let currency = GetPallet::<T::Currency>::pallet_mut(runtime);
+		currency.transfer(&caller, &target, schedule.locked)?;
+		Self::set_schedules(runtime, &target, schedules, locked);
+		Ok(())
+	}
+
+	/// Merge two vesting schedules of the caller into one, so the caller can receive more
+	/// schedules. The new schedule locks what is left of both, and ends when the later one would
+	/// have ended.
+	#[call_index(2)]
+	pub fn merge_schedules(
+		runtime: &mut T,
+		caller: T::AccountId,
+		schedule1_index: u32,
+		schedule2_index: u32,
+	) -> DispatchResult
+	where
+		T: GetPallet<Self> + GetPallet<T::Currency> + GetPallet<crate::system::Pallet<T>>,
+	{
+		let (first, second) = (schedule1_index as usize, schedule2_index as usize);
+		let now = GetPallet::<crate::system::Pallet<T>>::pallet(runtime).block_number();
+		let vesting = GetPallet::<Self>::pallet(runtime);
+		let schedules = vesting.vesting.get(&caller).ok_or(Error::<T>::NotVesting)?;
+		if first == second || first >= schedules.len() || second >= schedules.len() {
+			return Err(Error::<T>::ScheduleIndexOutOfBounds.into())
+		}
+
+		let merged = Self::merge(&schedules[first], &schedules[second], now)
+			.ok_or::<DispatchError>(Error::<T>::Overflow.into())?;
+		// The other schedules keep their order, and the merged schedule goes last.
+		let schedules = schedules
+			.iter()
+			.enumerate()
+			.filter(|(index, _)| *index != first && *index != second)
+			.map(|(_, schedule)| *schedule)
+			.chain([merged]);
+		let (schedules, locked) = Self::still_vesting(schedules, now)?;
+		Self::set_schedules(runtime, &caller, schedules, locked);
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Error, VestingInfo};
+	use crate::{
+		mock::{new_test_runtime, TestRuntime},
+		support::{Fungible, LockableCurrency},
+	};
+
+	type Vesting = super::Pallet<TestRuntime>;
+
+	fn schedule(
+		locked: u128,
+		per_block: u128,
+		starting_block: u32,
+	) -> super::VestingInfoOf<TestRuntime> {
+		VestingInfo { locked, per_block, starting_block }
+	}
+
+	#[test]
+	fn vested_transfer_and_vest() {
+		let mut runtime = new_test_runtime();
+		let (alice, bob) = ("alice".to_string(), "bob".to_string());
+		runtime.balances.set_balance(&alice, 100);
+
+		assert_eq!(
+			Vesting::vested_transfer(&mut runtime, alice.clone(), bob.clone(), schedule(30, 10, 2)),
+			Ok(())
+		);
+		assert_eq!(runtime.balances.balance(&alice), 70);
+		assert_eq!(runtime.balances.balance(&bob), 30);
+		assert_eq!(runtime.balances.frozen_balance(&bob), 30);
+		assert_eq!(runtime.vesting.vesting(&bob), &[schedule(30, 10, 2)]);
+
+		// Nothing vests before the starting block.
+		runtime.system.inc_block_number();
+		runtime.system.inc_block_number();
+		assert_eq!(Vesting::vest(&mut runtime, bob.clone()), Ok(()));
+		assert_eq!(runtime.balances.frozen_balance(&bob), 30);
+		assert_eq!(
+			Fungible::transfer(&mut runtime.balances, &bob, &alice, 10),
+			Err(crate::balances::Error::<TestRuntime>::LiquidityRestrictions.into())
+		);
+
+		// Funds are released every block, but only locked again when vesting.
+		runtime.system.inc_block_number();
+		assert_eq!(runtime.balances.frozen_balance(&bob), 30);
+		assert_eq!(Vesting::vest(&mut runtime, bob.clone()), Ok(()));
+		assert_eq!(runtime.balances.frozen_balance(&bob), 20);
+		assert_eq!(Fungible::transfer(&mut runtime.balances, &bob, &alice, 10), Ok(()));
+
+		// Once everything has vested, the schedule and the lock are removed.
+		runtime.system.inc_block_number();
+		runtime.system.inc_block_number();
+		assert_eq!(Vesting::vest(&mut runtime, bob.clone()), Ok(()));
+		assert_eq!(runtime.balances.frozen_balance(&bob), 0);
+		assert!(runtime.vesting.vesting(&bob).is_empty());
+		assert_eq!(Vesting::vest(&mut runtime, bob), Err(Error::<TestRuntime>::NotVesting.into()));
+	}
+
+	#[test]
+	fn vested_transfer_errors() {
+		let mut runtime = new_test_runtime();
+		let (alice, bob) = ("alice".to_string(), "bob".to_string());
+		runtime.balances.set_balance(&alice, 100);
+
+		assert_eq!(
+			Vesting::vested_transfer(&mut runtime, alice.clone(), bob.clone(), schedule(9, 1, 0)),
+			Err(Error::<TestRuntime>::AmountLow.into())
+		);
+		assert_eq!(
+			Vesting::vested_transfer(&mut runtime, alice.clone(), bob.clone(), schedule(10, 0, 0)),
+			Err(Error::<TestRuntime>::InvalidScheduleParams.into())
+		);
+		assert_eq!(
+			Vesting::vested_transfer(&mut runtime, alice.clone(), bob.clone(), schedule(200, 1, 0)),
+			Err(crate::balances::Error::<TestRuntime>::InsufficientBalance.into())
+		);
+
+		assert_eq!(
+			Vesting::vested_transfer(&mut runtime, alice.clone(), bob.clone(), schedule(10, 1, 0)),
+			Ok(())
+		);
+		assert_eq!(
+			Vesting::vested_transfer(&mut runtime, alice.clone(), bob.clone(), schedule(10, 1, 0)),
+			Ok(())
+		);
+		assert_eq!(
+			Vesting::vested_transfer(&mut runtime, alice, bob.clone(), schedule(10, 1, 0)),
+			Err(Error::<TestRuntime>::AtMaxVestingSchedules.into())
+		);
+		assert_eq!(runtime.vesting.vesting(&bob).len(), 2);
+		assert_eq!(runtime.balances.balance(&bob), 20);
+	}
+
+	#[test]
+	fn failed_calls_change_nothing() {
+		let mut runtime = new_test_runtime();
+		let (alice, bob) = ("alice".to_string(), "bob".to_string());
+		runtime.balances.set_balance(&alice, 100);
+		// Bob already has a schedule which locks so much that nothing more can be locked.
+		runtime.vesting.vesting.insert(bob.clone(), vec![schedule(u128::MAX, 1, 10)]);
+
+		// The transfer would overflow the lock, so nothing is transferred.
+		assert_eq!(
+			Vesting::vested_transfer(&mut runtime, alice.clone(), bob.clone(), schedule(10, 1, 0)),
+			Err(Error::<TestRuntime>::Overflow.into())
+		);
+		assert_eq!(runtime.balances.balance(&alice), 100);
+		assert_eq!(runtime.balances.balance(&bob), 0);
+		assert_eq!(runtime.vesting.vesting(&bob), &[schedule(u128::MAX, 1, 10)]);
+
+		// Vesting fails the same way, and keeps every schedule.
+		runtime
+			.vesting
+			.vesting
+			.insert(alice.clone(), vec![schedule(u128::MAX, 1, 10); 2]);
+		assert_eq!(
+			Vesting::vest(&mut runtime, alice.clone()),
+			Err(Error::<TestRuntime>::Overflow.into())
+		);
+		assert_eq!(runtime.vesting.vesting(&alice).len(), 2);
+	}
+
+	#[test]
+	fn several_schedules_and_merge() {
+		let mut runtime = new_test_runtime();
+		let (alice, bob) = ("alice".to_string(), "bob".to_string());
+		runtime.balances.set_balance(&alice, 100);
+
+		assert_eq!(
+			Vesting::vested_transfer(&mut runtime, alice.clone(), bob.clone(), schedule(20, 10, 0)),
+			Ok(())
+		);
+		assert_eq!(
+			Vesting::vested_transfer(&mut runtime, alice, bob.clone(), schedule(30, 5, 2)),
+			Ok(())
+		);
+		// Every schedule is locked under the same lock.
+		assert_eq!(runtime.balances.frozen_balance(&bob), 50);
+
+		runtime.system.inc_block_number();
+		assert_eq!(Vesting::vest(&mut runtime, bob.clone()), Ok(()));
+		assert_eq!(runtime.balances.frozen_balance(&bob), 40);
+
+		assert_eq!(
+			Vesting::merge_schedules(&mut runtime, bob.clone(), 0, 0),
+			Err(Error::<TestRuntime>::ScheduleIndexOutOfBounds.into())
+		);
+		assert_eq!(
+			Vesting::merge_schedules(&mut runtime, bob.clone(), 0, 2),
+			Err(Error::<TestRuntime>::ScheduleIndexOutOfBounds.into())
+		);
+
+		// The merged schedule locks what is left of both, from the latest start to the latest end.
+		assert_eq!(Vesting::merge_schedules(&mut runtime, bob.clone(), 1, 0), Ok(()));
+		assert_eq!(runtime.vesting.vesting(&bob), &[schedule(40, 6, 2)]);
+		assert_eq!(runtime.balances.frozen_balance(&bob), 40);
+	}
+
+	#[test]
+	fn parse_and_encode_schedule() {
+		use crate::support::{Decode, Encode};
+
+		let call =
+			"vested_transfer target=bob schedule=30,10,2".parse::<super::Call<TestRuntime>>();
+		assert_eq!(
+			call,
+			Ok(super::Call::vested_transfer {
+				target: "bob".to_string(),
+				schedule: schedule(30, 10, 2)
+			})
+		);
+		assert!("vested_transfer target=bob schedule=30,10"
+			.parse::<super::Call<TestRuntime>>()
+			.is_err());
+
+		let encoded = schedule(30, 10, 2).encode();
+		assert_eq!(encoded.len(), 16 + 16 + 4);
+		assert_eq!(
+			super::VestingInfoOf::<TestRuntime>::decode(&mut &encoded[..]),
+			Ok(schedule(30, 10, 2))
+		);
+	}
+}